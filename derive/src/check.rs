@@ -0,0 +1,195 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse::Parser, punctuated::Punctuated, spanned::Spanned, *};
+
+/// The arguments to a `#[mutatis::check(...)]` attribute.
+#[derive(Default)]
+struct CheckArgs {
+    /// The number of check iterations to run.
+    ///
+    /// ```ignore
+    /// #[mutatis::check(iters = 500)]
+    /// ```
+    iters: Option<Expr>,
+
+    /// The number of shrink iterations to run.
+    ///
+    /// ```ignore
+    /// #[mutatis::check(shrink_iters = 500)]
+    /// ```
+    shrink_iters: Option<Expr>,
+
+    /// The initial corpus.
+    ///
+    /// ```ignore
+    /// #[mutatis::check(corpus = [0, 1, 2])]
+    /// ```
+    corpus: Option<Expr>,
+}
+
+impl CheckArgs {
+    fn parse(args: Punctuated<MetaNameValue, Token![,]>) -> Result<Self> {
+        let mut result = CheckArgs::default();
+        for arg in args {
+            let slot = if arg.path.is_ident("iters") {
+                &mut result.iters
+            } else if arg.path.is_ident("shrink_iters") {
+                &mut result.shrink_iters
+            } else if arg.path.is_ident("corpus") {
+                &mut result.corpus
+            } else {
+                return Err(Error::new_spanned(
+                    &arg.path,
+                    "invalid `mutatis::check` argument: expected one of \
+                     `iters`, `shrink_iters`, or `corpus`",
+                ));
+            };
+            if slot.is_some() {
+                return Err(Error::new_spanned(
+                    &arg.path,
+                    "invalid `mutatis::check` argument: duplicate argument",
+                ));
+            }
+            *slot = Some(arg.value);
+        }
+        Ok(result)
+    }
+}
+
+pub fn expand_check(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let args =
+        CheckArgs::parse(Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse2(args)?)?;
+    let prop: ItemFn = parse2(item)?;
+
+    if let Some(asyncness) = &prop.sig.asyncness {
+        return Err(Error::new_spanned(
+            asyncness,
+            "`mutatis::check` properties cannot be `async`",
+        ));
+    }
+    if !prop.sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &prop.sig.generics,
+            "`mutatis::check` properties cannot be generic",
+        ));
+    }
+    if prop.sig.inputs.is_empty() {
+        return Err(Error::new_spanned(
+            &prop.sig,
+            "`mutatis::check` properties must take at least one argument",
+        ));
+    }
+
+    // Determine the owned type of each argument, and how to pass a reference
+    // to an owned value into the property function.
+    let mut tys = vec![];
+    let mut bindings = vec![];
+    let mut call_args = vec![];
+    for (i, input) in prop.sig.inputs.iter().enumerate() {
+        let ty = match input {
+            FnArg::Receiver(r) => {
+                return Err(Error::new_spanned(
+                    r,
+                    "`mutatis::check` properties cannot take `self`",
+                ))
+            }
+            FnArg::Typed(pat_ty) => &pat_ty.ty,
+        };
+        let binding = format_ident!("arg{i}", span = ty.span());
+        match &**ty {
+            Type::Reference(r) if r.mutability.is_none() => {
+                let elem = &r.elem;
+                tys.push(quote! { #elem });
+                call_args.push(quote! { #binding });
+            }
+            Type::Reference(r) => {
+                return Err(Error::new_spanned(
+                    r,
+                    "`mutatis::check` properties cannot take `&mut` arguments",
+                ))
+            }
+            ty => {
+                tys.push(quote! { #ty });
+                call_args.push(quote! { ::core::clone::Clone::clone(#binding) });
+            }
+        }
+        bindings.push(binding);
+    }
+
+    // A single argument is checked directly, multiple arguments are checked as
+    // a tuple.
+    let (value_ty, pattern) = if tys.len() == 1 {
+        let ty = &tys[0];
+        let binding = &bindings[0];
+        (quote! { #ty }, quote! { #binding })
+    } else {
+        (
+            quote! { ( #( #tys , )* ) },
+            quote! { ( #( #bindings , )* ) },
+        )
+    };
+
+    let name = &prop.sig.ident;
+    let name_str = name.to_string();
+    let vis = &prop.vis;
+
+    // Forward all attributes, e.g. `#[ignore]` or doc comments, to the
+    // generated test function.
+    let attrs = &prop.attrs;
+
+    let call = match &prop.sig.output {
+        ReturnType::Default => quote! {
+            {
+                #name( #( #call_args ),* );
+                ::core::result::Result::<(), &'static str>::Ok(())
+            }
+        },
+        ReturnType::Type(..) => quote! { #name( #( #call_args ),* ) },
+    };
+
+    let iters = args.iters.map(|iters| quote! { check.iters(#iters); });
+    let shrink_iters = args
+        .shrink_iters
+        .map(|shrink_iters| quote! { check.shrink_iters(#shrink_iters); });
+    let corpus = match args.corpus {
+        Some(corpus) => quote! { #corpus },
+        None => quote! { [<#value_ty as ::core::default::Default>::default()] },
+    };
+
+    let mut inner = prop.clone();
+    inner.attrs.clear();
+    inner.vis = Visibility::Inherited;
+
+    Ok(quote! {
+        #( #attrs )*
+        #[test]
+        #vis fn #name() {
+            #inner
+
+            let mut check = mutatis::check::Check::new();
+            #iters
+            #shrink_iters
+
+            let result = check.run_with(
+                mutatis::mutators::default::<#value_ty>(),
+                #corpus,
+                |#pattern: &#value_ty| #call,
+            );
+
+            match result {
+                ::core::result::Result::Ok(()) => {}
+                ::core::result::Result::Err(mutatis::check::CheckError::Failed(failure)) => {
+                    ::core::panic!(
+                        "property `{}` failed on input {:?}\n\n{}",
+                        #name_str,
+                        failure.value,
+                        failure.message,
+                    )
+                }
+                ::core::result::Result::Err(e) => {
+                    ::core::panic!("property `{}` could not be checked: {}", #name_str, e)
+                }
+            }
+        }
+    })
+}
@@ -4,6 +4,7 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{spanned::Spanned, *};
 
+mod check;
 mod container_attributes;
 mod field_attributes;
 use container_attributes::ContainerAttributes;
@@ -19,6 +20,16 @@ pub fn derive_mutator(tokens: proc_macro::TokenStream) -> proc_macro::TokenStrea
         .into()
 }
 
+#[proc_macro_attribute]
+pub fn check(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    check::expand_check(args.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 fn expand_derive_mutator(input: DeriveInput) -> Result<TokenStream> {
     let container_attrs = ContainerAttributes::from_derive_input(&input)?;
     let mutator_ty = MutatorType::new(&input, &container_attrs)?;
@@ -56,6 +67,9 @@ struct MutatorType {
     /// A vec of quoted bounds for the generics above, e.g. `A: Iterator<Item =
     /// B>,`.
     ty_generics_bounds: Vec<TokenStream>,
+
+    /// The names of the type's generic type parameters, e.g. `T`.
+    ty_params: Vec<Ident>,
 }
 
 impl MutatorType {
@@ -72,6 +86,7 @@ impl MutatorType {
         let mut ty_impl_generics = vec![];
         let mut ty_name_generics = vec![];
         let mut ty_generics_bounds = vec![];
+        let mut ty_params = vec![];
 
         for gen in &input.generics.params {
             match gen {
@@ -94,6 +109,7 @@ impl MutatorType {
                         ty_generics_bounds.push(quote! { #t });
                     }
                     let t = &t.ident;
+                    ty_params.push(t.clone());
                     ty_impl_generics.push(quote! { #t });
                     ty_name_generics.push(quote! { #t });
                 }
@@ -113,9 +129,34 @@ impl MutatorType {
             ty_impl_generics,
             ty_name_generics,
             ty_generics_bounds,
+            ty_params,
         })
     }
 
+    /// Should we emit a `DefaultMutate` bound for the given field type?
+    ///
+    /// Bounds on field types that do not mention any of the type's generic
+    /// type parameters are either trivially satisfied or trivially
+    /// unsatisfiable, so we omit them. This is necessary for recursive types,
+    /// where a bound like `Box<Self>: DefaultMutate` would otherwise require
+    /// proving itself.
+    ///
+    /// For the same reason, we also omit bounds on field types that mention
+    /// the type itself, as in `Box<List<T>>` inside a generic `List<T>`. Such
+    /// a bound holds whenever the bounds on the type's other fields do, so
+    /// omitting it does not lose anything.
+    fn needs_field_bound(&self, ty: &Type) -> bool {
+        fn mentions(tokens: TokenStream, idents: &[&Ident]) -> bool {
+            tokens.into_iter().any(|tt| match tt {
+                proc_macro2::TokenTree::Ident(i) => idents.contains(&&i),
+                proc_macro2::TokenTree::Group(g) => mentions(g.stream(), idents),
+                _ => false,
+            })
+        }
+        let params = self.ty_params.iter().collect::<Vec<_>>();
+        mentions(quote! { #ty }, &params) && !mentions(quote! { #ty }, &[&self.ty_name])
+    }
+
     fn mutator_impl_generics_iter(&self) -> impl Iterator<Item = TokenStream> + '_ {
         self.ty_impl_generics.iter().cloned().chain(
             self.mutator_fields
@@ -210,7 +251,7 @@ impl MutatorType {
                     let for_ty = &f.for_ty;
                     if let Some(g) = f.generic.as_ref() {
                         bounds.push(quote! { #g: mutatis::Mutate<#for_ty> });
-                    } else {
+                    } else if self.needs_field_bound(for_ty) {
                         debug_assert_eq!(f.behavior, FieldBehavior::DefaultMutate);
                         bounds.push(quote! { #for_ty: mutatis::DefaultMutate });
                     }
@@ -220,7 +261,7 @@ impl MutatorType {
                 for f in &self.mutator_fields {
                     if let Some(g) = f.generic.as_ref() {
                         bounds.push(quote! { #g: Default });
-                    } else {
+                    } else if self.needs_field_bound(&f.for_ty) {
                         let for_ty = &f.for_ty;
                         debug_assert_eq!(f.behavior, FieldBehavior::DefaultMutate);
                         bounds.push(quote! { #for_ty: mutatis::DefaultMutate });
@@ -230,7 +271,9 @@ impl MutatorType {
             WhereClauseKind::DefaultMutateBounds => {
                 for f in &self.mutator_fields {
                     let for_ty = &f.for_ty;
-                    bounds.push(quote! { #for_ty: mutatis::DefaultMutate });
+                    if self.needs_field_bound(for_ty) {
+                        bounds.push(quote! { #for_ty: mutatis::DefaultMutate });
+                    }
                 }
             }
         }
@@ -550,6 +593,10 @@ fn gen_mutator_impl(input: &DeriveInput, mutator_ty: &MutatorType) -> Result<Tok
     let ty_name = mutator_ty.ty_name_with_generics();
     let where_clause = mutator_ty.where_clause(WhereClauseKind::MutateBounds);
 
+    // Paths in patterns cannot have generic arguments without a turbofish, so
+    // use the bare type name and let them be inferred.
+    let ty_ident = &mutator_ty.ty_name;
+
     let mut fields_iter = mutator_ty.mutator_fields.iter();
     let mut make_mutation = |value| {
         let ident = &fields_iter.next().unwrap().ident;
@@ -615,7 +662,7 @@ fn gen_mutator_impl(input: &DeriveInput, mutator_ty: &MutatorType) -> Result<Tok
                             })
                             .collect::<Vec<_>>();
                         variants.push(quote! {
-                            #ty_name::#variant_ident { #( #patterns )* } => {
+                            #ty_ident::#variant_ident { #( #patterns )* } => {
                                 #( #mutates )*
                             }
                         });
@@ -639,7 +686,7 @@ fn gen_mutator_impl(input: &DeriveInput, mutator_ty: &MutatorType) -> Result<Tok
                             })
                             .collect::<Vec<_>>();
                         variants.push(quote! {
-                            #ty_name::#variant_ident( #( #patterns )* ) => {
+                            #ty_ident::#variant_ident( #( #patterns )* ) => {
                                 #( #mutates )*
                             }
                         });
@@ -647,7 +694,7 @@ fn gen_mutator_impl(input: &DeriveInput, mutator_ty: &MutatorType) -> Result<Tok
 
                     Fields::Unit => {
                         variants.push(quote! {
-                            #ty_name::#variant_ident => {}
+                            #ty_ident::#variant_ident => {}
                         });
                     }
                 }
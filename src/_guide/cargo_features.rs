@@ -9,14 +9,48 @@ want to enable `std`.**
   use features that the `alloc` crate provides.
 
 * **`std`**: Enable mutators for types in Rust's `std` crate and internally use
-  features that the `std` crate provides.
+  features that the `std` crate provides. This also enables the
+  `mutatis::reduce` module for standalone test-case reduction.
 
 * **`log`**: Enable logging with [the `log` crate](https://docs.rs/log).
 
 * **`check`**: Enable the `mutatis::check` module for writing property-based
   smoke tests with `mutatis`.
 
+* **`aflpp`**: Enable the `mutatis::aflpp` module and the
+  `mutatis::aflpp_custom_mutator!` macro for using `mutatis` mutators as AFL++
+  custom mutators.
+
+* **`libafl`**: Enable the `mutatis::libafl` module for using `mutatis`
+  mutators as LibAFL mutators. Note that LibAFL has a newer MSRV than this
+  crate.
+
+* **`libfuzzer`**: Enable the `mutatis::libfuzzer` module, which provides
+  ready-made glue for using `mutatis` mutators with `libfuzzer-sys`'s
+  `fuzz_mutator!` and `fuzz_crossover!` hooks.
+
+* **`arbitrary`**: Enable the `mutatis::mutators::from_arbitrary` mutator for
+  using types that implement [`arbitrary::Arbitrary`](https://docs.rs/arbitrary)
+  inside `mutatis` mutators.
+
+* **`bincode`**: Enable the `mutatis::codec::Bincode` codec for converting
+  between fuzzers' raw bytes and `serde`-serializable types with `bincode`.
+
+* **`postcard`**: Enable the `mutatis::codec::Postcard` codec for converting
+  between fuzzers' raw bytes and `serde`-serializable types with `postcard`.
+
+* **`proptest`**: Enable the `mutatis::proptest` module for using `mutatis`
+  mutators as [`proptest`](https://docs.rs/proptest) strategies. Note that
+  `proptest` has a newer MSRV than this crate.
+
+* **`quickcheck`**: Enable the `mutatis::quickcheck` module, whose
+  `Mutated<T>` wrapper implements [`quickcheck`](https://docs.rs/quickcheck)'s
+  `Arbitrary` trait via `mutatis` mutators. Note that `quickcheck` has a newer
+  MSRV than this crate.
+
 * **`derive`**: Enable the `#[derive(Mutate)]` macro for automatically deriving
-  mutators for your types
+  mutators for your types. When the `check` feature is also enabled, this
+  additionally enables the `#[mutatis::check]` attribute macro for defining
+  property-based tests.
 
  */
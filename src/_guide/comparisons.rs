@@ -51,7 +51,7 @@ Note that part (1) of the goal approach is your own responsibility, being highly
 domain-specific and requiring deep knowledge of the system under test. Helping
 you with (2) is the primary reason why `mutatis` (and
 [`arbitrary`](https://docs.rs/arbitrary)) exist. The
-[`mutatis::check`][crate::check] module also provides a tiny framework for doing
+[`mutatis::check`][mod@crate::check] module also provides a tiny framework for doing
 (3) for you, but you can always use something else instead. And (4) should be
 just [a small bit of glue code][crate::_guide::fuzzer_integration], once you've
 selected a coverage-guided fuzzing engine, such as libfuzzer.
@@ -374,7 +374,7 @@ maintained or mostly-maintained by the same person: this author. Looking at our
 goal approach for testing, `arbitrary`'s main purpose is also to help you do
 (2), just like `mutatis`. The [`arbtest`][arbtest] crate provides a mini
 property-based testing framework on top of `arbitrary` which can help you do
-(3), similar to the [`mutatis::check`][crate::check] module. And, once again,
+(3), similar to the [`mutatis::check`][mod@crate::check] module. And, once again,
 similar to `mutatis`, (4) is just a little bit of glue code to integrate with
 `libfuzzer`. The two crates live in the same niche and have the same high-level
 goals, they just take slightly different approaches to achieve them.
@@ -402,6 +402,11 @@ see which you vibe with, which feels easier to build with and maintain, and
 which results in higher fuzzing throughput and deeper coverage. Or use both, if
 you can spare the effort.
 
+If you already have `Arbitrary` implementations for some of your types, you
+don't have to choose: with the `arbitrary` cargo feature enabled,
+[`m::from_arbitrary`][crate::mutators::from_arbitrary] lets you use those types
+as leaves inside your `mutatis` mutators.
+
 [arbitrary]: https://docs.rs/arbitrary
 [arbtest]: https://docs.rs/arbtest
 
@@ -433,7 +438,7 @@ case, and you cannot initialize the corpus with arbitrary test cases you happen
 to have on hand (like `sqlite.h` for testing your C compiler). It also means
 that if you change the implementation of your generator, then the corpus doesn't
 reflect the set of values that it previously did, just like with `arbitrary`. On
-the other hand, the [`mutatis::check`][crate::check] mini property-based testing
+the other hand, the [`mutatis::check`][mod@crate::check] mini property-based testing
 framework allows you to supply any set of values you want, directly, as the
 initial corpus. This means that you don't need separate unit tests for your
 known edge cases, you just throw them in the initial corpus.
@@ -465,7 +470,7 @@ with no limit on the number of mutation strategies for a given type.
 cases that triggered historical failures are checked going forward. You cannot
 supply initial edge cases that you always want to check. You need to write
 separate unit tests for these things. On the other hand, the
-[`mutatis::check`][crate::check] mini property-based testing framework allows
+[`mutatis::check`][mod@crate::check] mini property-based testing framework allows
 you to supply any set of values you want, directly, as the initial corpus
 including test cases that triggered historical failures and any other edge case
 you can think of.
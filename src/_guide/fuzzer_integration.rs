@@ -19,7 +19,10 @@ or property-based testing framework of choice:
      otherwise have a natural way of doing that, like if you're fuzzing a parser
      and could just run the parser on the raw data, then a quick-and-easy trick
      to to use `serde` and `bincode` to deserialize the raw bytes into your
-     structured type.
+     structured type. The [`mutatis::codec`][crate::codec] module's
+     [`Codec`][crate::codec::Codec] trait captures this conversion, and
+     bundles ready-made codecs for `bincode` and `postcard` behind cargo
+     features of the same names.
 
   2. Run your `mutatis`-based custom mutator on the structured test case.
 
@@ -114,11 +117,16 @@ fuzz_mutator!(|data: &mut [u8], size: usize, max_size: usize, seed: u32| {
     // Mutate `rgb` using its default, derived mutator!
     match session.mutate(&mut rgb) {
         Ok(()) => {
-            // Update the fuzzer's raw data based on the mutated RGB color.
+            // Update the fuzzer's raw data based on the mutated RGB color. If
+            // it doesn't fit, fall back to the fuzzer's default mutation
+            // strategies, rather than truncating it into a different test
+            // case.
             let new_bytes = rgb.to_bytes();
-            let new_size = std::cmp::min(max_size, new_bytes.len());
-            data[..new_size].copy_from_slice(&bytes[..new_size]);
-            new_size
+            if new_bytes.len() > max_size {
+                return fuzzer_mutate(data, size, max_size);
+            }
+            data[..new_bytes.len()].copy_from_slice(&new_bytes);
+            new_bytes.len()
         }
         Err(_) => {
             // If we failed to mutate the test case, fall back to the fuzzer's
@@ -130,6 +138,29 @@ fuzz_mutator!(|data: &mut [u8], size: usize, max_size: usize, seed: u32| {
 # }
 ```
 
+If you enable this crate's `libfuzzer` cargo feature, then the
+[`mutatis::libfuzzer`][crate::libfuzzer] module provides ready-made
+implementations of the `fuzz_mutator!` and `fuzz_crossover!` glue above, given
+a [`Codec`][crate::codec::Codec] for converting between raw bytes and your
+structured test case type. With that feature enabled, the whole custom mutator
+above becomes:
+
+```rust,ignore
+fuzz_mutator!(|data: &mut [u8], size: usize, max_size: usize, seed: u32| {
+    mutatis::libfuzzer::mutate::<Rgb, RgbCodec>(data, size, max_size, seed)
+});
+```
+
+The ready-made implementation also passes libFuzzer's `max_size` through to
+the mutation [`Session`][crate::Session] via
+[`Session::max_size`][crate::Session::max_size], so that mutators avoid
+growing test cases beyond what libFuzzer allows in the first place. When
+writing your own glue, you can do the same by configuring your session with
+`max_size` and mutating with
+[`Session::mutate_sized_with`][crate::Session::mutate_sized_with], which
+estimates your test case's current size with the
+[`SizeHint`][crate::SizeHint] trait.
+
 [libfuzzer]: https://crates.io/crates/libfuzzer-sys
 [fuzz-mutator]: https://docs.rs/libfuzzer-sys/latest/libfuzzer_sys/macro.fuzz_mutator.html
 
@@ -10,4 +10,17 @@ The minimum supported Rust version (MSRV) is currently **1.80.0**.
 The MSRV will never be increased in a patch release, but may be increased in a
 minor release. We will aim to avoid doing so without good reason.
 
+Some optional cargo features depend on crates with a newer MSRV than this
+crate's. Enabling those features requires whichever Rust version those crates
+require:
+
+* **`libafl`**: LibAFL requires Rust 1.87 or newer.
+
+* **`proptest`**: Recent versions of `proptest` require Rust 1.88 or newer.
+
+* **`quickcheck`**: Recent versions of `quickcheck` require Rust 1.85 or newer.
+
+These features are tested with recent Rust versions only, not with this crate's
+MSRV.
+
  */
@@ -136,4 +136,7 @@ for _ in 0..4 {
 * [`Check::shrink_iters`][crate::check::Check::shrink_iters]: Configure the
   number of attempts to shrink a failing input before reporting the failure.
 
+* [`Reducer`][crate::reduce::Reducer]: Minimize an input, such as a crash found
+  by a fuzzer, given a mutator and an "is still interesting" predicate.
+
  */
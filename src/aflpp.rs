@@ -0,0 +1,334 @@
+//! Support for using `mutatis` mutators as AFL++ custom mutators.
+//!
+//! [AFL++](https://aflplus.plus/) loads custom mutators from shared libraries
+//! that export a set of C-ABI functions, such as `afl_custom_init` and
+//! `afl_custom_fuzz`. The [`aflpp_custom_mutator!`][crate::aflpp_custom_mutator]
+//! macro exports those functions for you, given a test case type that
+//! implements [`DefaultMutate`] and a [`Codec`] for converting between AFL++'s
+//! raw bytes and that type.
+//!
+//! Under the hood, the exported functions drive a [`CustomMutator`], which you
+//! can also use directly if you need more control.
+//!
+//! # Example
+//!
+//! First, create a crate for your custom mutator that builds a shared library,
+//! by adding the following to its `Cargo.toml`:
+//!
+//! ```toml
+//! [lib]
+//! crate-type = ["cdylib"]
+//! ```
+//!
+//! Then, in that crate's `src/lib.rs`, export the custom mutator:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # mod example {
+//! use mutatis::{codec::Codec, Mutate};
+//!
+//! /// A red-green-blue color.
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Mutate)]
+//! pub struct Rgb([u8; 3]);
+//!
+//! /// Encode `Rgb` colors as their three raw bytes.
+//! pub struct RgbCodec;
+//!
+//! impl Codec<Rgb> for RgbCodec {
+//!     fn decode(bytes: &[u8]) -> Option<Rgb> {
+//!         bytes.first_chunk::<3>().map(|b| Rgb(*b))
+//!     }
+//!
+//!     fn encode(rgb: &Rgb, bytes: &mut Vec<u8>) {
+//!         bytes.extend_from_slice(&rgb.0);
+//!     }
+//! }
+//!
+//! // Export the AFL++ custom mutator functions for `Rgb`.
+//! mutatis::aflpp_custom_mutator!(Rgb, RgbCodec);
+//! # }
+//! ```
+//!
+//! Finally, build the shared library and point AFL++ at it:
+//!
+//! ```sh
+//! cargo build --release
+//! AFL_CUSTOM_MUTATOR_LIBRARY=target/release/libmy_mutator.so afl-fuzz ...
+//! ```
+
+use super::*;
+use crate::codec::Codec;
+use crate::log;
+use core::ffi::{c_uint, c_void};
+use core::marker::PhantomData;
+use std::boxed::Box;
+use std::panic::{self, AssertUnwindSafe};
+use std::vec::Vec;
+
+/// A custom mutator for AFL++.
+///
+/// Decodes AFL++'s test cases into `T` values with the codec `C`, mutates them
+/// with the mutator `M`, and encodes them back into raw bytes.
+///
+/// You generally don't need to use this type directly; instead use the
+/// [`aflpp_custom_mutator!`][crate::aflpp_custom_mutator] macro, which exports
+/// the C-ABI functions that AFL++ expects and drives a `CustomMutator` from
+/// them.
+pub struct CustomMutator<T, C, M = <T as DefaultMutate>::DefaultMutate>
+where
+    T: DefaultMutate,
+{
+    session: Session,
+    mutator: M,
+    out: Vec<u8>,
+    _phantom: PhantomData<fn(T) -> C>,
+}
+
+impl<T, C, M> core::fmt::Debug for CustomMutator<T, C, M>
+where
+    T: DefaultMutate,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CustomMutator")
+            .field("session", &self.session)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, C> CustomMutator<T, C>
+where
+    T: DefaultMutate,
+    C: Codec<T>,
+{
+    /// Create a new custom mutator that uses `T`'s default mutator and the
+    /// given seed for its random number generator.
+    pub fn new(seed: u32) -> Self {
+        Self::with_mutator(seed, T::DefaultMutate::default())
+    }
+}
+
+impl<T, C, M> CustomMutator<T, C, M>
+where
+    T: DefaultMutate,
+    C: Codec<T>,
+    M: Mutate<T>,
+{
+    /// Create a new custom mutator that uses the given mutator and seed.
+    pub fn with_mutator(seed: u32, mutator: M) -> Self {
+        CustomMutator {
+            session: Session::new().seed(seed.into()),
+            mutator,
+            out: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get this custom mutator's session.
+    ///
+    /// The session persists across calls to [`fuzz`][CustomMutator::fuzz], so
+    /// this can be used to record [comparison operands][Session::cmp_log]
+    /// observed while running the program under test, or to report
+    /// [feedback][Session::feedback] for [adaptive scheduling][Session::adaptive],
+    /// which subsequent mutations will then take into account.
+    pub fn session(&mut self) -> &mut Session {
+        &mut self.session
+    }
+
+    /// Mutate the given test case, returning the new test case.
+    ///
+    /// The mutator's session is given `max_size` as its
+    /// [maximum size][Session::max_size], and the length of `buf` as the
+    /// value's current size, so that mutators avoid growing the value beyond
+    /// what AFL++ allows.
+    ///
+    /// The new test case is never larger than `max_size`. If the given test
+    /// case cannot be decoded, the mutator fails, or the encoded mutated value
+    /// would be larger than `max_size`, then the returned test case is empty,
+    /// which tells AFL++ to skip this mutation.
+    pub fn fuzz(&mut self, buf: &[u8], max_size: usize) -> &[u8] {
+        self.out.clear();
+
+        let mut value = match C::decode(buf) {
+            Some(v) => v,
+            None => {
+                log::trace!("failed to decode test case, skipping mutation");
+                return &[];
+            }
+        };
+
+        // Pass AFL++'s size budget through to the mutators, using the test
+        // case's encoded size as the value's current size.
+        self.session.context.max_size = Some(max_size);
+        self.session.context.begin_mutation();
+        let mutator = &mut self.mutator;
+        let result = self
+            .session
+            .context
+            .with_size(buf.len(), |ctx| ctx.mutate_with(mutator, &mut value));
+        if let Err(e) = result {
+            log::trace!("failed to mutate test case, skipping mutation: {e}");
+            return &[];
+        }
+
+        C::encode(&value, &mut self.out);
+        if self.out.len() > max_size {
+            log::trace!(
+                "mutated test case is too large ({} > {max_size}), skipping mutation",
+                self.out.len()
+            );
+            self.out.clear();
+        }
+
+        &self.out
+    }
+}
+
+/// Export the C-ABI functions for an AFL++ custom mutator.
+///
+/// Takes the test case type, which must implement [`DefaultMutate`], and a
+/// [`Codec`] for converting between AFL++'s raw bytes and that type.
+///
+/// This defines and exports the following functions:
+///
+/// * `afl_custom_init`: Creates a [`CustomMutator`] seeded with AFL++'s seed.
+///
+/// * `afl_custom_fuzz`: Mutates a test case with the `CustomMutator`. If
+///   decoding, mutating, or encoding the test case panics, the panic is
+///   caught and the mutation is skipped.
+///
+/// * `afl_custom_deinit`: Destroys the `CustomMutator`.
+///
+/// This macro should only be invoked once per shared library, since the
+/// functions it defines are exported under fixed symbol names.
+///
+/// See [the `aflpp` module's documentation][crate::aflpp] for example usage.
+#[macro_export]
+macro_rules! aflpp_custom_mutator {
+    ( $ty:ty , $codec:ty $(,)? ) => {
+        /// Auto-generated function. Do not use; only for AFL++'s consumption.
+        #[no_mangle]
+        #[doc(hidden)]
+        pub unsafe extern "C" fn afl_custom_init(
+            afl: *mut ::core::ffi::c_void,
+            seed: ::core::ffi::c_uint,
+        ) -> *mut ::core::ffi::c_void {
+            $crate::aflpp::init::<$ty, $codec>(afl, seed)
+        }
+
+        /// Auto-generated function. Do not use; only for AFL++'s consumption.
+        #[no_mangle]
+        #[doc(hidden)]
+        pub unsafe extern "C" fn afl_custom_fuzz(
+            data: *mut ::core::ffi::c_void,
+            buf: *mut u8,
+            buf_size: usize,
+            out_buf: *mut *mut u8,
+            add_buf: *mut u8,
+            add_buf_size: usize,
+            max_size: usize,
+        ) -> usize {
+            $crate::aflpp::fuzz::<$ty, $codec>(
+                data,
+                buf,
+                buf_size,
+                out_buf,
+                add_buf,
+                add_buf_size,
+                max_size,
+            )
+        }
+
+        /// Auto-generated function. Do not use; only for AFL++'s consumption.
+        #[no_mangle]
+        #[doc(hidden)]
+        pub unsafe extern "C" fn afl_custom_deinit(data: *mut ::core::ffi::c_void) {
+            $crate::aflpp::deinit::<$ty, $codec>(data)
+        }
+    };
+}
+
+/// Implementation of `afl_custom_init`. Do not use; only for the
+/// `aflpp_custom_mutator!` macro.
+///
+/// # Safety
+///
+/// The returned pointer must only be passed to `fuzz` and `deinit` with the
+/// same `T` and `C` type parameters.
+#[doc(hidden)]
+pub unsafe fn init<T, C>(_afl: *mut c_void, seed: c_uint) -> *mut c_void
+where
+    T: DefaultMutate,
+    C: Codec<T>,
+{
+    // `unsigned int` is generally a `u32`, but not on all targets. Do an
+    // infallible (and potentially lossy, but that's okay because it preserves
+    // determinism) conversion.
+    #[allow(clippy::unnecessary_cast)]
+    let seed = seed as u32;
+    let mutator = Box::new(CustomMutator::<T, C>::new(seed));
+    Box::into_raw(mutator).cast()
+}
+
+/// Implementation of `afl_custom_fuzz`. Do not use; only for the
+/// `aflpp_custom_mutator!` macro.
+///
+/// # Safety
+///
+/// `data` must have been returned by `init` with the same `T` and `C` type
+/// parameters, and not yet passed to `deinit`. `buf` must be valid for reads
+/// of `buf_size` bytes, and `out_buf` must be valid for writes.
+///
+/// Panics while decoding, mutating, or encoding the test case are caught
+/// rather than unwinding across the FFI boundary; the mutation is skipped by
+/// returning an empty test case.
+#[doc(hidden)]
+pub unsafe fn fuzz<T, C>(
+    data: *mut c_void,
+    buf: *mut u8,
+    buf_size: usize,
+    out_buf: *mut *mut u8,
+    _add_buf: *mut u8,
+    _add_buf_size: usize,
+    max_size: usize,
+) -> usize
+where
+    T: DefaultMutate,
+    C: Codec<T>,
+{
+    let mutator = &mut *data.cast::<CustomMutator<T, C>>();
+    let buf = if buf_size == 0 {
+        &[]
+    } else {
+        core::slice::from_raw_parts(buf, buf_size)
+    };
+
+    // Unwinding out of an `extern "C"` function aborts the whole AFL++
+    // process, so catch panics from codecs (e.g. when `Bincode` fails to
+    // encode a value) and mutators and skip this mutation instead.
+    let len = match panic::catch_unwind(AssertUnwindSafe(|| mutator.fuzz(buf, max_size).len())) {
+        Ok(len) => len,
+        Err(_) => {
+            log::warn!("panicked while mutating test case, skipping mutation");
+            mutator.out.clear();
+            0
+        }
+    };
+    *out_buf = mutator.out.as_mut_ptr();
+    len
+}
+
+/// Implementation of `afl_custom_deinit`. Do not use; only for the
+/// `aflpp_custom_mutator!` macro.
+///
+/// # Safety
+///
+/// `data` must have been returned by `init` with the same `T` and `C` type
+/// parameters, and must not be used again afterwards.
+#[doc(hidden)]
+pub unsafe fn deinit<T, C>(data: *mut c_void)
+where
+    T: DefaultMutate,
+    C: Codec<T>,
+{
+    drop(Box::from_raw(data.cast::<CustomMutator<T, C>>()));
+}
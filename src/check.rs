@@ -74,13 +74,45 @@
 //! # fn hsl_to_rgb(h: u8, s: u8, l: u8) -> [u8; 3] { [0, 0, 0] }
 //! }
 //! ```
+//!
+//! When the `derive` feature is also enabled, the
+//! [`#[mutatis::check]`][macro@crate::check] attribute macro can generate the
+//! `#[test]` function, `Check` configuration, and failure reporting for you:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # mod example {
+//! #[mutatis::check(iters = 1000, corpus = [[0x00, 0x00, 0x00], [0xff, 0xff, 0xff]])]
+//! fn rgb_to_hsl_to_rgb_round_trip(rgb: &[u8; 3]) -> Result<(), &'static str> {
+//!     let [r, g, b] = *rgb;
+//!     let [h, s, l] = rgb_to_hsl(r, g, b);
+//!     if hsl_to_rgb(h, s, l) == *rgb {
+//!         Ok(())
+//!     } else {
+//!         Err("round-trip conversion failed!")
+//!     }
+//! }
+//! # fn rgb_to_hsl(r: u8, g: u8, b: u8) -> [u8; 3] { [r, g, b] }
+//! # fn hsl_to_rgb(h: u8, s: u8, l: u8) -> [u8; 3] { [h, s, l] }
+//! # }
+//! ```
 
 use super::*;
 use crate::log;
 use crate::mutators as m;
+use crate::reduce::Reducer;
+use std::cell::RefCell;
+use std::collections::{hash_map::DefaultHasher, BTreeMap, HashSet};
 use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
 use std::panic;
 use std::prelude::v1::*;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+mod differential;
+mod state_machine;
+pub use state_machine::*;
 
 /// The result of running a check.
 ///
@@ -106,6 +138,10 @@ pub enum CheckError<T> {
     EmptyCorpus,
 
     /// An error occurred while running the check.
+    ///
+    /// Individual mutator errors are skipped and counted in
+    /// [`CheckStats::mutator_errors`]; this is only returned when the mutator
+    /// never succeeded in mutating any corpus entry.
     MutatorError(Error),
 }
 
@@ -235,13 +271,228 @@ where
 
 impl<T> std::error::Error for CheckFailure<T> where T: Debug {}
 
+std::thread_local! {
+    static LABELS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Attach the given classification `label` to the input currently being
+/// checked, if `condition` is `true`.
+///
+/// Call this function from inside a property that is being run by a [`Check`]
+/// to describe the input that the property was given. The number of inputs
+/// with each label is reported in the run's [`CheckStats`], which lets you
+/// verify that your check is actually exercising the cases you care about.
+///
+/// Calling this function outside of a running `Check` has no effect.
+///
+/// # Example
+///
+/// ```
+/// use mutatis::{check::{self, Check}, mutators as m};
+///
+/// let (result, stats) = Check::new().run_with_stats(m::i32(), [0], |x: &i32| {
+///     check::classify(*x < 0, "negative");
+///     check::classify(*x == 0, "zero");
+///     check::classify(*x > 0, "positive");
+///     Ok::<_, String>(())
+/// });
+/// result.unwrap();
+///
+/// println!("{stats}");
+///
+/// // Example output:
+/// //
+/// //     checked 1001 inputs
+/// //     final corpus size: 1
+/// //     mutator exhausted: 0 times
+/// //     mutator errors: 0
+/// //     labels:
+/// //       50.05% (501) positive
+/// //       49.85% (499) negative
+/// //        0.10% (1) zero
+/// ```
+pub fn classify(condition: bool, label: impl ToString) {
+    if condition {
+        LABELS.with(|labels| labels.borrow_mut().push(label.to_string()));
+    }
+}
+
+/// Attach the given value's `Debug` representation as a classification label
+/// for the input currently being checked.
+///
+/// This is equivalent to `classify(true, format!("{value:?}"))` and is useful
+/// for collecting a histogram of some property of the inputs, for example their
+/// length.
+///
+/// # Example
+///
+/// ```
+/// use mutatis::{check::{self, Check}, mutators as m};
+///
+/// let (result, stats) = Check::new().run_with_stats(m::array(m::u8()), [[0, 0]], |xs: &[u8; 2]| {
+///     check::collect(xs.iter().filter(|x| **x == 0).count());
+///     Ok::<_, String>(())
+/// });
+/// result.unwrap();
+///
+/// for (label, count) in &stats.labels {
+///     println!("{count} inputs contained {label} zeroes");
+/// }
+/// ```
+pub fn collect(value: impl Debug) {
+    classify(true, format!("{value:?}"));
+}
+
+fn take_labels() -> Vec<String> {
+    LABELS.with(|labels| std::mem::take(&mut *labels.borrow_mut()))
+}
+
+/// Statistics gathered while running a [`Check`].
+///
+/// Get the statistics for a run of a `Check` by running it with the
+/// [`Check::run_with_stats`] or [`Check::run_with_feedback_and_stats`]
+/// methods. The statistics are also logged at the `info` level at the end of
+/// every run when the `log` feature is enabled.
+///
+/// The `Display` implementation renders a human-readable summary, including a
+/// histogram of the labels attached via [`classify`] and [`collect`].
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct CheckStats {
+    /// The number of inputs that the property was checked against, including
+    /// the initial corpus but excluding shrinking attempts.
+    pub inputs_checked: usize,
+
+    /// The number of inputs that were given each label.
+    pub labels: BTreeMap<String, usize>,
+
+    /// The size of the corpus at the start of each mutation iteration.
+    pub corpus_sizes: Vec<usize>,
+
+    /// The number of times that the mutator was exhausted for a corpus entry,
+    /// causing that entry to be removed from the corpus.
+    pub exhausted: usize,
+
+    /// The number of mutator errors that were skipped, either during mutation
+    /// iterations or while shrinking.
+    pub mutator_errors: usize,
+}
+
+impl CheckStats {
+    fn record_labels(&mut self) {
+        self.inputs_checked += 1;
+        let mut labels = take_labels();
+        // Only count each label once per input.
+        labels.sort();
+        labels.dedup();
+        for label in labels {
+            *self.labels.entry(label).or_insert(0) += 1;
+        }
+    }
+}
+
+impl fmt::Display for CheckStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "checked {} inputs", self.inputs_checked)?;
+        if let Some(size) = self.corpus_sizes.last() {
+            writeln!(f, "final corpus size: {size}")?;
+        }
+        writeln!(f, "mutator exhausted: {} times", self.exhausted)?;
+        write!(f, "mutator errors: {}", self.mutator_errors)?;
+
+        if !self.labels.is_empty() {
+            write!(f, "\nlabels:")?;
+            let mut labels = self.labels.iter().collect::<Vec<_>>();
+            labels.sort_by(|(a, x), (b, y)| y.cmp(x).then(a.cmp(b)));
+            let total = self.inputs_checked.max(1) as f64;
+            for (label, count) in labels {
+                let percent = *count as f64 / total * 100.0;
+                write!(f, "\n  {percent:6.2}% ({count}) {label}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// A check that can be run to test a property.
 ///
-/// See [the module-level documentation][crate::check] for example usage.
+/// See [the module-level documentation][mod@crate::check] for example usage.
+///
+/// # Environment Variables
+///
+/// A `Check`'s configuration can be overridden at run time with the following
+/// environment variables:
+///
+/// * `MUTATIS_ITERS`: the number of check iterations to run, overriding
+///   [`Check::iters`].
+///
+/// * `MUTATIS_SHRINK_ITERS`: the number of shrink iterations to run,
+///   overriding [`Check::shrink_iters`].
+///
+/// * `MUTATIS_SEED`: the RNG seed to use, overriding [`Check::seed`].
+///
+/// * `MUTATIS_TIME_BUDGET`: the maximum number of seconds (which may be
+///   fractional, e.g. `0.5`) to spend on check iterations, overriding
+///   [`Check::time_budget`].
+///
+/// Environment variables take precedence over values configured via builder
+/// methods, which in turn take precedence over the defaults. This lets the same
+/// test suite run a quick smoke pass in CI and a deep pass nightly, for example
+/// by running `MUTATIS_ITERS=1000000 MUTATIS_TIME_BUDGET=3600 cargo test`.
+///
+/// An environment variable whose value cannot be parsed causes the check to
+/// panic, rather than silently falling back to a different configuration.
 #[derive(Debug)]
 pub struct Check {
     iters: usize,
     shrink_iters: usize,
+    shrink_until_fixpoint: bool,
+    seed: Option<u64>,
+    time_budget: Option<Duration>,
+}
+
+/// The effective configuration for a run of a `Check`, after applying any
+/// environment variable overrides.
+#[derive(Debug, PartialEq)]
+struct Config {
+    iters: usize,
+    shrink_iters: usize,
+    seed: Option<u64>,
+    time_budget: Option<Duration>,
+}
+
+impl Config {
+    fn session(&self) -> Session {
+        match self.seed {
+            Some(seed) => Session::new().seed(seed),
+            None => Session::new(),
+        }
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.time_budget.map(|budget| Instant::now() + budget)
+    }
+}
+
+fn parse_env_var<T>(name: &str, value: Option<String>) -> Option<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let value = value?;
+    match value.trim().parse() {
+        Ok(x) => Some(x),
+        Err(e) => panic!("invalid value for `{name}` environment variable ({value:?}): {e}"),
+    }
+}
+
+fn past_deadline(deadline: Option<Instant>) -> bool {
+    let past = deadline.is_some_and(|d| Instant::now() >= d);
+    if past {
+        log::info!("time budget exhausted, stopping check iterations");
+    }
+    past
 }
 
 impl Default for Check {
@@ -256,6 +507,42 @@ impl Check {
         Check {
             iters: 1000,
             shrink_iters: 1000,
+            shrink_until_fixpoint: false,
+            seed: None,
+            time_budget: None,
+        }
+    }
+
+    fn config(&self) -> Config {
+        self.config_with_env(|name| std::env::var(name).ok())
+    }
+
+    fn config_with_env(&self, mut env: impl FnMut(&str) -> Option<String>) -> Config {
+        let mut var = |name| (name, env(name));
+
+        let (name, value) = var("MUTATIS_TIME_BUDGET");
+        let time_budget = parse_env_var::<f64>(name, value)
+            .map(|secs| {
+                Duration::try_from_secs_f64(secs).unwrap_or_else(|e| {
+                    panic!("invalid value for `{name}` environment variable ({secs}): {e}")
+                })
+            })
+            .or(self.time_budget);
+
+        let (name, value) = var("MUTATIS_ITERS");
+        let iters = parse_env_var(name, value).unwrap_or(self.iters);
+
+        let (name, value) = var("MUTATIS_SHRINK_ITERS");
+        let shrink_iters = parse_env_var(name, value).unwrap_or(self.shrink_iters);
+
+        let (name, value) = var("MUTATIS_SEED");
+        let seed = parse_env_var(name, value).or(self.seed);
+
+        Config {
+            iters,
+            shrink_iters,
+            seed,
+            time_budget,
         }
     }
 
@@ -272,6 +559,54 @@ impl Check {
         self
     }
 
+    /// Configure whether to keep shrinking a failing input until it cannot be
+    /// shrunk any further.
+    ///
+    /// Shrinking first applies random shrinking mutations and, once those stop
+    /// making progress, sweeps over every candidate shrinking mutation of the
+    /// current input in turn, accepting each smaller input that still fails
+    /// the property and continuing the sweep from there. Shrinking stops early
+    /// when a full sweep over every candidate finds no smaller failing input.
+    ///
+    /// When this is `true`, the [`shrink_iters`][Check::shrink_iters] budget is
+    /// ignored and shrinking only stops upon reaching such a fixpoint. This
+    /// usually yields much smaller counterexamples, at the cost of potentially
+    /// many more property evaluations. It is best-effort, however: candidates
+    /// that make random changes, such as choosing a random smaller integer,
+    /// are only tried once per sweep, so the result is not guaranteed to be
+    /// minimal. See [`Reducer`] for details.
+    ///
+    /// Defaults to `false`.
+    pub fn shrink_until_fixpoint(&mut self, fixpoint: bool) -> &mut Check {
+        self.shrink_until_fixpoint = fixpoint;
+        self
+    }
+
+    /// Configure the seed for the random number generator, making the check's
+    /// mutations deterministic.
+    ///
+    /// By default, every run within a process uses a different seed, taken
+    /// from a process-wide counter. Since that counter starts at the same
+    /// value in every process, the default seeds are the same across
+    /// processes.
+    pub fn seed(&mut self, seed: u64) -> &mut Check {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Configure the maximum amount of time to spend on check iterations.
+    ///
+    /// Once the time budget is exhausted, no more check iterations are run,
+    /// even if fewer than the configured number of [`iters`][Check::iters] have
+    /// been run. The time budget does not apply to checking the initial corpus
+    /// nor to shrinking a failing input.
+    ///
+    /// By default, there is no time budget.
+    pub fn time_budget(&mut self, time_budget: Duration) -> &mut Check {
+        self.time_budget = Some(time_budget);
+        self
+    }
+
     /// Run this configured `Check` with a default initial `T` value and the
     /// default mutator.
     ///
@@ -312,6 +647,41 @@ impl Check {
     /// shrinking via the [`shrink_iters`][Check::shrink_iters] method.
     pub fn run_with<M, T, S>(
         &self,
+        mutator: M,
+        initial_corpus: impl IntoIterator<Item = T>,
+        property: impl FnMut(&T) -> std::result::Result<(), S>,
+    ) -> CheckResult<T>
+    where
+        M: Mutate<T>,
+        T: Clone + Debug,
+        S: ToString,
+    {
+        self.run_with_stats(mutator, initial_corpus, property).0
+    }
+
+    /// Run this configured `Check` with the given corpus and mutator, and also
+    /// return the statistics gathered during the run.
+    ///
+    /// This is like [`run_with`][Check::run_with], but additionally returns
+    /// the run's [`CheckStats`], including the histogram of labels attached
+    /// via [`classify`] and [`collect`].
+    pub fn run_with_stats<M, T, S>(
+        &self,
+        mutator: M,
+        initial_corpus: impl IntoIterator<Item = T>,
+        property: impl FnMut(&T) -> std::result::Result<(), S>,
+    ) -> (CheckResult<T>, CheckStats)
+    where
+        M: Mutate<T>,
+        T: Clone + Debug,
+        S: ToString,
+    {
+        Self::with_stats(|stats| self.run_with_impl(stats, mutator, initial_corpus, property))
+    }
+
+    fn run_with_impl<M, T, S>(
+        &self,
+        stats: &mut CheckStats,
         mut mutator: M,
         initial_corpus: impl IntoIterator<Item = T>,
         mut property: impl FnMut(&T) -> std::result::Result<(), S>,
@@ -329,103 +699,332 @@ impl Check {
         // First, double check that the property is maintained for all values in
         // the initial corpus.
         for value in &corpus {
-            if let Err(msg) = Self::check_one(value, &mut property) {
-                return self.shrink(mutator, value.clone(), property, msg);
+            let result = Self::check_one(value, &mut property);
+            stats.record_labels();
+            if let Err(msg) = result {
+                return self.shrink(stats, mutator, value.clone(), property, msg);
             }
         }
 
         // Second, run the check on mutated values derived from the corpus for
         // the configured iterations.
-        let mut session = Session::new();
-        for _ in 0..self.iters {
+        let config = self.config();
+        let deadline = config.deadline();
+        let mut session = config.session();
+        let mut mutated = false;
+        let mut last_error = None;
+        for _ in 0..config.iters {
+            if past_deadline(deadline) {
+                break;
+            }
+            stats.corpus_sizes.push(corpus.len());
             let index = session.context.rng().gen_index(corpus.len()).unwrap();
 
             match session.mutate_with(&mut mutator, &mut corpus[index]) {
-                Ok(()) => {}
+                Ok(()) => mutated = true,
                 Err(e) if e.is_exhausted() => {
+                    stats.exhausted += 1;
                     corpus.swap_remove(index);
                     if corpus.is_empty() {
                         return Ok(());
                     }
+                    continue;
+                }
+                Err(e) => {
+                    log::warn!("skipping mutator error: {e}");
+                    stats.mutator_errors += 1;
+                    last_error = Some(e);
+                    continue;
                 }
-                Err(e) => return Err(e.into()),
             }
 
-            if let Err(msg) = Self::check_one(&corpus[index], &mut property) {
-                return self.shrink(mutator, corpus[index].clone(), property, msg);
+            let result = Self::check_one(&corpus[index], &mut property);
+            stats.record_labels();
+            if let Err(msg) = result {
+                return self.shrink(stats, mutator, corpus[index].clone(), property, msg);
             }
         }
 
-        Ok(())
+        Self::finish(mutated, last_error)
     }
 
-    fn check_one<T, S>(
-        value: &T,
-        mut property: impl FnMut(&T) -> std::result::Result<(), S>,
-    ) -> std::result::Result<(), String>
+    /// Run this configured `Check` with the given corpus and mutator, using
+    /// novelty feedback from the property to grow the corpus.
+    ///
+    /// This is similar to [`run_with`][Check::run_with], except that the
+    /// `property` returns a set of labels describing the behavior it observed
+    /// when processing the given input, for example which branches were taken
+    /// or which kind of AST node was at the root. Labels may be any type that
+    /// implements `Hash`; only their hashes are retained.
+    ///
+    /// Whenever a mutated value produces a label that has not been seen before,
+    /// that value is added to the corpus as a new entry, rather than replacing
+    /// the corpus entry it was derived from. This keeps interesting
+    /// intermediate values around as starting points for further mutation,
+    /// giving a lightweight, coverage-like feedback loop without requiring any
+    /// compiler instrumentation. Values that do not produce any new labels
+    /// replace their parent in the corpus, just like in `run_with`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mutatis::{check::Check, mutators as m};
+    ///
+    /// let result = Check::new().run_with_feedback(
+    ///     m::array(m::u8()),
+    ///     [[0, 0, 0, 0]],
+    ///     |bytes: &[u8; 4]| {
+    ///         // Label each input with how long of a prefix of `b"FUZZ"` it
+    ///         // matches. Inputs that match longer prefixes are new behaviors
+    ///         // that get saved in the corpus.
+    ///         let matched = bytes
+    ///             .iter()
+    ///             .zip(b"FUZZ")
+    ///             .take_while(|(a, b)| a == b)
+    ///             .count();
+    ///         if matched == 4 {
+    ///             Err("found the magic bytes")
+    ///         } else {
+    ///             Ok([matched])
+    ///         }
+    ///     },
+    /// );
+    ///
+    /// // Whether or not we found the magic bytes depends on the random
+    /// // mutations that were chosen.
+    /// # let _ = result;
+    /// ```
+    pub fn run_with_feedback<M, T, S, I>(
+        &self,
+        mutator: M,
+        initial_corpus: impl IntoIterator<Item = T>,
+        property: impl FnMut(&T) -> std::result::Result<I, S>,
+    ) -> CheckResult<T>
     where
-        T: Debug,
+        M: Mutate<T>,
+        T: Clone + Debug,
         S: ToString,
+        I: IntoIterator,
+        I::Item: Hash,
     {
-        match panic::catch_unwind(panic::AssertUnwindSafe(|| property(value))) {
-            Ok(Ok(())) => Ok(()),
-            Ok(Err(msg)) => Err(msg.to_string()),
-            Err(_) => Err("<panicked>".into()),
-        }
+        self.run_with_feedback_and_stats(mutator, initial_corpus, property)
+            .0
     }
 
-    fn shrink<M, T, S>(
+    /// Run this configured `Check` with novelty feedback, and also return the
+    /// statistics gathered during the run.
+    ///
+    /// This is like [`run_with_feedback`][Check::run_with_feedback], but
+    /// additionally returns the run's [`CheckStats`], whose
+    /// [`corpus_sizes`][CheckStats::corpus_sizes] show how the corpus grew.
+    pub fn run_with_feedback_and_stats<M, T, S, I>(
+        &self,
+        mutator: M,
+        initial_corpus: impl IntoIterator<Item = T>,
+        property: impl FnMut(&T) -> std::result::Result<I, S>,
+    ) -> (CheckResult<T>, CheckStats)
+    where
+        M: Mutate<T>,
+        T: Clone + Debug,
+        S: ToString,
+        I: IntoIterator,
+        I::Item: Hash,
+    {
+        Self::with_stats(|stats| {
+            self.run_with_feedback_impl(stats, mutator, initial_corpus, property)
+        })
+    }
+
+    fn run_with_feedback_impl<M, T, S, I>(
         &self,
+        stats: &mut CheckStats,
         mut mutator: M,
-        mut value: T,
-        mut property: impl FnMut(&T) -> std::result::Result<(), S>,
-        mut message: String,
+        initial_corpus: impl IntoIterator<Item = T>,
+        mut property: impl FnMut(&T) -> std::result::Result<I, S>,
     ) -> CheckResult<T>
     where
         M: Mutate<T>,
         T: Clone + Debug,
         S: ToString,
+        I: IntoIterator,
+        I::Item: Hash,
     {
-        log::warn!("failed on input {value:?}: {message}");
-        if self.shrink_iters == 0 {
-            return Err(CheckFailure { value, message }.into());
+        let mut corpus = initial_corpus.into_iter().collect::<Vec<_>>();
+        if corpus.is_empty() {
+            return Err(CheckError::EmptyCorpus);
         }
 
-        log::debug!("shrinking for {} iters...", self.shrink_iters);
+        let mut seen = HashSet::new();
+        let mut is_novel = |labels: I| {
+            let mut novel = false;
+            for label in labels {
+                let mut hasher = DefaultHasher::new();
+                label.hash(&mut hasher);
+                novel |= seen.insert(hasher.finish());
+            }
+            novel
+        };
 
-        let mut session = Session::new().shrink(true);
+        // First, check all values in the initial corpus, recording the labels
+        // they produce.
+        for value in &corpus {
+            let result = Self::check_one(value, &mut property);
+            stats.record_labels();
+            match result {
+                Ok(labels) => {
+                    is_novel(labels);
+                }
+                Err(msg) => {
+                    let property = |v: &T| property(v).map(|_| ());
+                    return self.shrink(stats, mutator, value.clone(), property, msg);
+                }
+            }
+        }
 
-        for _ in 0..self.shrink_iters {
-            let mut candidate = value.clone();
+        // Second, mutate copies of corpus entries, keeping copies that produce
+        // new labels and replacing their parents with those that don't.
+        let config = self.config();
+        let deadline = config.deadline();
+        let mut session = config.session();
+        let mut mutated = false;
+        let mut last_error = None;
+        for _ in 0..config.iters {
+            if past_deadline(deadline) {
+                break;
+            }
+            stats.corpus_sizes.push(corpus.len());
+            let index = session.context.rng().gen_index(corpus.len()).unwrap();
 
+            let mut candidate = corpus[index].clone();
             match session.mutate_with(&mut mutator, &mut candidate) {
-                // If the mutator is exhausted, then don't keep trying to shrink
-                // the input and just report the final error.
-                Err(e) if e.is_exhausted() => break,
-
-                // Ignore mutator errors during shrinking because it is more
-                // important to report the property failure.
+                Ok(()) => mutated = true,
+                Err(e) if e.is_exhausted() => {
+                    stats.exhausted += 1;
+                    corpus.swap_remove(index);
+                    if corpus.is_empty() {
+                        return Ok(());
+                    }
+                    continue;
+                }
                 Err(e) => {
-                    log::info!("got mutator error during shrinking, ignoring: {e}");
+                    log::warn!("skipping mutator error: {e}");
+                    stats.mutator_errors += 1;
+                    last_error = Some(e);
                     continue;
                 }
-
-                Ok(()) => {}
             }
 
-            match Self::check_one(&candidate, &mut property) {
-                Ok(()) => {
-                    // Not a failure, throw away this candidate and try another
-                    // mutation.
+            let result = Self::check_one(&candidate, &mut property);
+            stats.record_labels();
+            match result {
+                Ok(labels) => {
+                    if is_novel(labels) {
+                        log::debug!("found new behavior with input {candidate:?}");
+                        corpus.push(candidate);
+                    } else {
+                        corpus[index] = candidate;
+                    }
                 }
                 Err(msg) => {
-                    message = msg;
-                    log::debug!("got failure for shrunken input {value:?}: {message}");
-                    value = candidate;
+                    let property = |v: &T| property(v).map(|_| ());
+                    return self.shrink(stats, mutator, candidate, property, msg);
                 }
             }
         }
 
+        Self::finish(mutated, last_error)
+    }
+
+    /// Finish a run that did not find any failures, reporting a mutator error
+    /// only if the mutator never succeeded.
+    fn finish<T>(mutated: bool, last_error: Option<Error>) -> CheckResult<T> {
+        match last_error {
+            Some(e) if !mutated => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn with_stats<T>(
+        run: impl FnOnce(&mut CheckStats) -> CheckResult<T>,
+    ) -> (CheckResult<T>, CheckStats) {
+        // Discard any labels left over from outside of a check.
+        let _ = take_labels();
+
+        let mut stats = CheckStats::default();
+        let result = run(&mut stats);
+        log::info!("check statistics:\n{stats}");
+        (result, stats)
+    }
+
+    fn check_one<T, R, S>(
+        value: &T,
+        mut property: impl FnMut(&T) -> std::result::Result<R, S>,
+    ) -> std::result::Result<R, String>
+    where
+        T: Debug,
+        S: ToString,
+    {
+        match panic::catch_unwind(panic::AssertUnwindSafe(|| property(value))) {
+            Ok(Ok(r)) => Ok(r),
+            Ok(Err(msg)) => Err(msg.to_string()),
+            Err(_) => Err("<panicked>".into()),
+        }
+    }
+
+    fn shrink<M, T, S>(
+        &self,
+        stats: &mut CheckStats,
+        mutator: M,
+        value: T,
+        mut property: impl FnMut(&T) -> std::result::Result<(), S>,
+        mut message: String,
+    ) -> CheckResult<T>
+    where
+        M: Mutate<T>,
+        T: Clone + Debug,
+        S: ToString,
+    {
+        log::warn!("failed on input {value:?}: {message}");
+        let config = self.config();
+
+        let mut reducer = Reducer::new();
+        if self.shrink_until_fixpoint {
+            log::debug!("shrinking until fixpoint...");
+        } else {
+            log::debug!("shrinking for {} iters...", config.shrink_iters);
+            reducer.max_iters(config.shrink_iters);
+        }
+        if let Some(seed) = config.seed {
+            reducer.seed(seed);
+        }
+
+        // Every failing candidate is accepted by the reducer, so the last
+        // failure message we see is the message for the final shrunken value.
+        let (value, progress) = reducer.reduce_impl(
+            mutator,
+            value,
+            |_| None,
+            // We only require `T: Debug` of property inputs, so compare them by
+            // their `Debug` representations. This only happens for candidates
+            // that still fail the property, which the reducer is about to
+            // accept unless they are unchanged.
+            |a, b| format!("{a:?}") == format!("{b:?}"),
+            |candidate| {
+                let result = Self::check_one(candidate, &mut property);
+                // Labels attached while shrinking are not counted in the stats.
+                let _ = take_labels();
+                match result {
+                    Ok(()) => false,
+                    Err(msg) => {
+                        log::debug!("got failure for shrunken input {candidate:?}: {msg}");
+                        message = msg;
+                        true
+                    }
+                }
+            },
+        );
+
+        stats.mutator_errors += progress.mutator_errors;
         log::info!("shrunk failing input down to {value:?}");
         Err(CheckFailure { value, message }.into())
     }
@@ -503,6 +1102,52 @@ mod tests {
         assert_eq!(failure.message, "expected < 10");
     }
 
+    #[test]
+    fn check_shrink_until_fixpoint_ignores_shrink_iters() {
+        let failure = check()
+            .shrink_iters(0)
+            .shrink_until_fixpoint(true)
+            .run_with(m::u8(), [u8::MAX], |x: &u8| {
+                if *x < 10 {
+                    Ok(())
+                } else {
+                    Err("expected < 10")
+                }
+            })
+            .unwrap_err()
+            .unwrap_failed();
+
+        assert_eq!(failure.value, 10);
+        assert_eq!(failure.message, "expected < 10");
+    }
+
+    #[test]
+    fn check_shrink_stops_at_fixpoint() {
+        // A mutator whose only shrinking mutation never changes the value, so
+        // shrinking can never make progress.
+        struct Stuck;
+        impl Mutate<u32> for Stuck {
+            fn mutate(&mut self, c: &mut Candidates, _value: &mut u32) -> crate::Result<()> {
+                c.mutation(|_ctx| Ok(()))
+            }
+        }
+
+        let mut evals = 0;
+        let failure = check()
+            .shrink_iters(usize::MAX)
+            .run_with(Stuck, [42], |_: &u32| {
+                evals += 1;
+                Err("always fails")
+            })
+            .unwrap_err()
+            .unwrap_failed();
+
+        // Unchanged candidates are still checked against the property, but
+        // never accepted, so shrinking terminates.
+        assert_eq!(failure.value, 42);
+        assert!(evals < 10_000, "evals = {evals}");
+    }
+
     #[test]
     fn check_run_with_fail_on_panic() {
         let result = check().run_with(m::bool(), [true], |_: &bool| -> Result<(), String> {
@@ -526,4 +1171,271 @@ mod tests {
         assert_eq!(failure.value, 10);
         assert_eq!(failure.message, "<panicked>");
     }
+
+    #[test]
+    fn check_run_with_feedback_okay() {
+        check()
+            .run_with_feedback(m::u8(), [0], |x: &u8| -> Result<_, String> { Ok([*x % 4]) })
+            .unwrap();
+    }
+
+    #[test]
+    fn check_run_with_feedback_fail_and_shrink() {
+        let failure = check()
+            .shrink_iters(1000)
+            .run_with_feedback(m::u8(), [0], |x: &u8| {
+                if *x < 10 {
+                    Ok([*x])
+                } else {
+                    Err("expected < 10")
+                }
+            })
+            .unwrap_err()
+            .unwrap_failed();
+
+        assert_eq!(failure.value, 10);
+        assert_eq!(failure.message, "expected < 10");
+    }
+
+    #[test]
+    fn check_run_with_feedback_keeps_novel_values() {
+        // Finding the failure requires matching each byte in turn; each
+        // matched prefix is a new label that gets saved in the corpus.
+        let failure = check()
+            .iters(100_000)
+            .shrink_iters(0)
+            .run_with_feedback(m::array(m::range(0..=3)), [[0u8; 4]], |bytes: &[u8; 4]| {
+                let matched = bytes
+                    .iter()
+                    .zip([1, 2, 3, 1])
+                    .take_while(|(a, b)| **a == *b)
+                    .count();
+                if matched == 4 {
+                    Err("found it")
+                } else {
+                    Ok([matched])
+                }
+            })
+            .unwrap_err()
+            .unwrap_failed();
+
+        assert_eq!(failure.value, [1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn check_stats() {
+        let (result, stats) = check().iters(100).run_with_stats(
+            m::range(0..=9),
+            [0],
+            |x: &u8| -> Result<(), String> {
+                classify(*x < 5, "small");
+                classify(*x >= 5, "large");
+                collect(*x % 2 == 0);
+                Ok(())
+            },
+        );
+        result.unwrap();
+
+        assert_eq!(stats.inputs_checked, 101);
+        assert_eq!(stats.corpus_sizes, vec![1; 100]);
+        assert_eq!(stats.exhausted, 0);
+        assert_eq!(stats.mutator_errors, 0);
+        assert_eq!(stats.labels["small"] + stats.labels["large"], 101);
+        assert_eq!(stats.labels["true"] + stats.labels["false"], 101);
+    }
+
+    #[test]
+    fn check_stats_exhausted() {
+        let (result, stats) =
+            check().run_with_stats(m::unit(), [()], |_: &()| -> Result<(), String> { Ok(()) });
+        result.unwrap();
+
+        assert_eq!(stats.inputs_checked, 1);
+        assert_eq!(stats.exhausted, 1);
+        assert!(stats.labels.is_empty());
+    }
+
+    #[test]
+    fn check_stats_mutator_errors() {
+        // Fail every other mutation; the failures are skipped and counted.
+        let mut calls = 0;
+        let mutator = m::u32().map(|_c: &mut Context, _x: &mut u32| {
+            calls += 1;
+            if calls % 2 == 0 {
+                Err(Error::other("oops"))
+            } else {
+                Ok(())
+            }
+        });
+        let (result, stats) = check()
+            .iters(100)
+            .run_with_stats(mutator, [0], |_: &u32| Ok::<_, String>(()));
+        result.unwrap();
+
+        assert_eq!(stats.mutator_errors, 50);
+        assert_eq!(stats.inputs_checked, 51);
+    }
+
+    #[test]
+    fn check_mutator_that_always_fails() {
+        let mutator = m::u32().map(|_c: &mut Context, _x: &mut u32| Err(Error::other("oops")));
+        let (result, stats) = check()
+            .iters(10)
+            .run_with_stats(mutator, [0], |_: &u32| Ok::<_, String>(()));
+
+        result.unwrap_err().unwrap_mutator_error();
+        assert_eq!(stats.mutator_errors, 10);
+        assert_eq!(stats.inputs_checked, 1);
+    }
+
+    #[test]
+    fn check_config_precedence() {
+        let no_env = |_: &str| None;
+
+        // Defaults.
+        let c = Check::new();
+        assert_eq!(
+            c.config_with_env(no_env),
+            Config {
+                iters: 1000,
+                shrink_iters: 1000,
+                seed: None,
+                time_budget: None,
+            }
+        );
+
+        // Builder methods override defaults.
+        let mut c = Check::new();
+        c.iters(5)
+            .shrink_iters(6)
+            .seed(7)
+            .time_budget(Duration::from_secs(8));
+        assert_eq!(
+            c.config_with_env(no_env),
+            Config {
+                iters: 5,
+                shrink_iters: 6,
+                seed: Some(7),
+                time_budget: Some(Duration::from_secs(8)),
+            }
+        );
+
+        // Environment variables override builder methods.
+        let env = |name: &str| {
+            Some(
+                match name {
+                    "MUTATIS_ITERS" => "50",
+                    "MUTATIS_SHRINK_ITERS" => "60",
+                    "MUTATIS_SEED" => "70",
+                    "MUTATIS_TIME_BUDGET" => "0.5",
+                    _ => return None,
+                }
+                .to_string(),
+            )
+        };
+        assert_eq!(
+            c.config_with_env(env),
+            Config {
+                iters: 50,
+                shrink_iters: 60,
+                seed: Some(70),
+                time_budget: Some(Duration::from_millis(500)),
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid value for `MUTATIS_ITERS` environment variable")]
+    fn check_config_invalid_env_var() {
+        Check::new().config_with_env(|name| (name == "MUTATIS_ITERS").then(|| "lots".to_string()));
+    }
+
+    #[test]
+    fn check_time_budget() {
+        check()
+            .iters(usize::MAX)
+            .time_budget(Duration::from_millis(10))
+            .run_with(m::u8(), [0], |_: &u8| -> Result<(), String> { Ok(()) })
+            .unwrap();
+    }
+
+    #[test]
+    fn check_seed_is_deterministic() {
+        let run = || {
+            let mut values = vec![];
+            check()
+                .iters(10)
+                .seed(42)
+                .run_with(m::u32(), [0], |x: &u32| -> Result<(), String> {
+                    values.push(*x);
+                    Ok(())
+                })
+                .unwrap();
+            values
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn check_run_differential_okay() {
+        check()
+            .run_differential(
+                m::u32(),
+                [0],
+                |x: &u32| x.count_ones(),
+                |x: &u32| (0..32).filter(|i| x & (1 << i) != 0).count() as u32,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn check_run_differential_fail_and_shrink() {
+        let failure = check()
+            .run_differential(
+                m::u8(),
+                [u8::MAX],
+                |x: &u8| x.saturating_mul(2),
+                |x: &u8| x.wrapping_mul(2),
+            )
+            .unwrap_err()
+            .unwrap_failed();
+
+        assert_eq!(failure.value, 128);
+        assert_eq!(
+            failure.message,
+            "implementations diverged: implementation A returned 255, \
+             implementation B returned 0"
+        );
+    }
+
+    #[test]
+    fn check_run_differential_one_panics() {
+        let failure = check()
+            .run_differential(
+                m::u8(),
+                [u8::MAX],
+                |x: &u8| x.checked_add(1),
+                |x: &u8| Some(x.checked_add(1).expect("overflow")),
+            )
+            .unwrap_err()
+            .unwrap_failed();
+
+        assert_eq!(failure.value, u8::MAX);
+        assert!(failure.message.starts_with(
+            "implementations diverged: implementation A returned None, \
+                          implementation B panicked: "
+        ));
+    }
+
+    #[test]
+    fn check_run_differential_both_panic() {
+        check()
+            .run_differential(
+                m::range(1..=9),
+                [1],
+                |x: &u8| x.checked_add(u8::MAX).expect("overflow in A"),
+                |x: &u8| x.checked_add(u8::MAX).expect("overflow in B"),
+            )
+            .unwrap();
+    }
 }
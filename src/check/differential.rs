@@ -0,0 +1,93 @@
+//! Differential testing of two implementations against each other.
+//!
+//! See [`Check::run_differential`] for details.
+
+use super::*;
+use std::any::Any;
+
+impl Check {
+    /// Run a differential check, asserting that two implementations of the
+    /// same functionality agree on every input.
+    ///
+    /// This is a convenience for the common property "the new implementation
+    /// agrees with the reference implementation". Each input is passed to both
+    /// `impl_a` and `impl_b`, and the check fails if their outputs are not
+    /// equal. If exactly one of the implementations panics, that is also
+    /// considered a divergence and fails the check; if both panic, they are
+    /// considered to agree.
+    ///
+    /// On failure, the input is shrunk to a minimal input on which the
+    /// implementations still diverge, and the failure message reports both
+    /// implementations' outputs (or panic messages) for that input.
+    ///
+    /// The `mutator` and `initial_corpus` are used just like in
+    /// [`run_with`][Check::run_with].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mutatis::{check::Check, mutators as m};
+    ///
+    /// fn reference_popcount(x: u32) -> u32 {
+    ///     (0..32).filter(|i| x & (1 << i) != 0).count() as u32
+    /// }
+    ///
+    /// fn fast_popcount(x: u32) -> u32 {
+    ///     x.count_ones()
+    /// }
+    ///
+    /// Check::new()
+    ///     .run_differential(
+    ///         m::u32(),
+    ///         [0, 1, u32::MAX],
+    ///         |x: &u32| reference_popcount(*x),
+    ///         |x: &u32| fast_popcount(*x),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn run_differential<M, T, O>(
+        &self,
+        mutator: M,
+        initial_corpus: impl IntoIterator<Item = T>,
+        mut impl_a: impl FnMut(&T) -> O,
+        mut impl_b: impl FnMut(&T) -> O,
+    ) -> CheckResult<T>
+    where
+        M: Mutate<T>,
+        T: Clone + Debug,
+        O: Debug + PartialEq,
+    {
+        self.run_with(mutator, initial_corpus, |value: &T| {
+            let a = panic::catch_unwind(panic::AssertUnwindSafe(|| impl_a(value)));
+            let b = panic::catch_unwind(panic::AssertUnwindSafe(|| impl_b(value)));
+            match (a, b) {
+                (Ok(a), Ok(b)) if a == b => Ok(()),
+                (Err(_), Err(_)) => Ok(()),
+                (Ok(a), Ok(b)) => Err(format!(
+                    "implementations diverged: implementation A returned {a:?}, \
+                     implementation B returned {b:?}"
+                )),
+                (Ok(a), Err(b)) => Err(format!(
+                    "implementations diverged: implementation A returned {a:?}, \
+                     implementation B panicked: {}",
+                    panic_message(&*b)
+                )),
+                (Err(a), Ok(b)) => Err(format!(
+                    "implementations diverged: implementation A panicked: {}, \
+                     implementation B returned {b:?}",
+                    panic_message(&*a)
+                )),
+            }
+        })
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "<unknown panic payload>"
+    }
+}
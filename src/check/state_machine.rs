@@ -0,0 +1,227 @@
+//! Stateful, model-based testing.
+//!
+//! See [`Check::run_state_machine`] for details.
+
+use super::*;
+use crate::error::ResultExt;
+
+/// A state machine that operations can be applied to.
+///
+/// This trait is implemented by both the reference model and the system under
+/// test in a [model-based check][Check::run_state_machine]. Applying an
+/// operation updates the state machine's internal state and returns an
+/// observation, such as the operation's return value or a snapshot of the
+/// machine's visible state. The observations produced by the model and the
+/// system under test are compared after every operation.
+pub trait StateMachine<Op> {
+    /// The observation produced by applying an operation.
+    type Observation;
+
+    /// Apply the given operation to this state machine, returning an
+    /// observation.
+    fn apply(&mut self, op: &Op) -> Self::Observation;
+}
+
+impl Check {
+    /// Run a stateful, model-based check.
+    ///
+    /// Rather than checking a property of a single input, this checks that a
+    /// system under test behaves the same as a (usually much simpler)
+    /// reference model when both are given the same sequence of operations.
+    ///
+    /// * `op_mutator` mutates individual operations. The typical operation type
+    ///   is an `enum` with one variant per API call, and a mutator
+    ///   `#[derive(Mutate)]`d for it.
+    ///
+    /// * `initial_corpus` provides the initial operation sequences. New
+    ///   sequences are derived from these by removing, duplicating, and
+    ///   mutating operations, as well as by inserting copies of any operation
+    ///   from the initial corpus. Because derived `enum` mutators do not change
+    ///   which variant a value is, the initial corpus should contain at least
+    ///   one of each kind of operation that you want to be exercised.
+    ///
+    /// * `model` and `sut` create a fresh reference model and system under
+    ///   test, respectively, for each operation sequence that is checked.
+    ///
+    /// For every operation in a sequence, the operation is applied to both the
+    /// model and the system under test, and the resulting observations are
+    /// compared. If they differ, or if either panics, the check fails. The
+    /// failing operation sequence is then shrunk by removing operations and
+    /// simplifying the remaining operations with `op_mutator`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "derive")]
+    /// # mod example {
+    /// use mutatis::{check::{Check, StateMachine}, mutators as m, Mutate};
+    /// use std::collections::VecDeque;
+    ///
+    /// #[derive(Clone, Debug, Mutate)]
+    /// enum Op {
+    ///     Push(u32),
+    ///     Pop,
+    ///     Len,
+    /// }
+    ///
+    /// /// The reference model: a plain `VecDeque`.
+    /// #[derive(Default)]
+    /// struct Model(VecDeque<u32>);
+    ///
+    /// impl StateMachine<Op> for Model {
+    ///     type Observation = Option<u32>;
+    ///
+    ///     fn apply(&mut self, op: &Op) -> Option<u32> {
+    ///         match op {
+    ///             Op::Push(x) => {
+    ///                 self.0.push_back(*x);
+    ///                 None
+    ///             }
+    ///             Op::Pop => self.0.pop_front(),
+    ///             Op::Len => Some(self.0.len() as u32),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// /// The system under test: a fancy ring buffer.
+    /// #[derive(Default)]
+    /// struct RingBuffer {
+    ///     // ...
+    /// #   inner: VecDeque<u32>,
+    /// }
+    ///
+    /// impl StateMachine<Op> for RingBuffer {
+    ///     type Observation = Option<u32>;
+    ///
+    ///     fn apply(&mut self, op: &Op) -> Option<u32> {
+    ///         // ...
+    /// #       match op {
+    /// #           Op::Push(x) => { self.inner.push_back(*x); None }
+    /// #           Op::Pop => self.inner.pop_front(),
+    /// #           Op::Len => Some(self.inner.len() as u32),
+    /// #       }
+    ///     }
+    /// }
+    ///
+    /// pub fn ring_buffer_matches_model() {
+    ///     Check::new()
+    ///         .run_state_machine(
+    ///             m::default::<Op>(),
+    ///             [vec![Op::Push(1), Op::Pop, Op::Len]],
+    ///             Model::default,
+    ///             RingBuffer::default,
+    ///         )
+    ///         .unwrap();
+    /// }
+    /// # }
+    /// # #[cfg(feature = "derive")] example::ring_buffer_matches_model();
+    /// ```
+    pub fn run_state_machine<M, Op, Model, Sut>(
+        &self,
+        op_mutator: M,
+        initial_corpus: impl IntoIterator<Item = Vec<Op>>,
+        mut model: impl FnMut() -> Model,
+        mut sut: impl FnMut() -> Sut,
+    ) -> CheckResult<Vec<Op>>
+    where
+        M: Mutate<Op>,
+        Op: Clone + Debug,
+        Model: StateMachine<Op>,
+        Sut: StateMachine<Op, Observation = Model::Observation>,
+        Model::Observation: Debug + PartialEq,
+    {
+        let initial_corpus = initial_corpus.into_iter().collect::<Vec<_>>();
+        let pool = initial_corpus.iter().flatten().cloned().collect();
+        self.run_with(
+            Ops { op_mutator, pool },
+            initial_corpus,
+            |ops: &Vec<Op>| -> std::result::Result<(), String> {
+                let mut model = model();
+                let mut sut = sut();
+                for (i, op) in ops.iter().enumerate() {
+                    let expected = model.apply(op);
+                    let actual = sut.apply(op);
+                    if expected != actual {
+                        return Err(format!(
+                            "observations differ after operation {i} ({op:?}): \
+                             model observed {expected:?}, system under test \
+                             observed {actual:?}"
+                        ));
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+/// A mutator for sequences of operations.
+///
+/// This only requires a `Mutate<Op>` implementation, not a `Generate<Op>`
+/// implementation, because new operations are created by copying and then
+/// mutating existing operations. Copies are taken from either the sequence
+/// being mutated or a pool of all the operations in the initial corpus; the
+/// latter ensures that a kind of operation is never lost for good just because
+/// every instance of it happened to be removed from the corpus's sequences.
+struct Ops<M, Op> {
+    op_mutator: M,
+    pool: Vec<Op>,
+}
+
+impl<M, Op> Mutate<Vec<Op>> for Ops<M, Op>
+where
+    M: Mutate<Op>,
+    Op: Clone,
+{
+    fn mutate(&mut self, c: &mut Candidates, ops: &mut Vec<Op>) -> crate::Result<()> {
+        let len = ops.len();
+
+        // Remove a single operation.
+        for i in 0..len {
+            c.named_mutation("ops.remove", |_| {
+                ops.remove(i);
+                Ok(())
+            })?;
+        }
+
+        // Remove all operations after a given point. This lets shrinking
+        // quickly discard the operations after the failing one.
+        if c.shrink() {
+            for i in 1..len {
+                c.named_mutation("ops.truncate", |_| {
+                    ops.truncate(i);
+                    Ok(())
+                })?;
+            }
+        }
+
+        // Simplify or otherwise mutate a single operation.
+        for op in ops.iter_mut() {
+            self.op_mutator.mutate(c, op)?;
+        }
+
+        // Duplicate an operation, or copy an operation from the pool, inserting
+        // the copy at a random position, and then mutate the copy. Operations
+        // are not required to implement `SizeHint`, so estimate the growth by
+        // their in-memory size.
+        if !c.shrink() && c.can_grow(core::mem::size_of::<Op>()) {
+            let mut insert = |ctx: &mut Context, ops: &mut Vec<Op>, op: Op| {
+                let j = ctx.rng().gen_index(ops.len() + 1).unwrap();
+                ops.insert(j, op);
+                ctx.mutate_with(&mut self.op_mutator, &mut ops[j])
+                    .ignore_exhausted()
+            };
+            for i in 0..len {
+                c.named_mutation("ops.duplicate", |ctx| insert(ctx, ops, ops[i].clone()))?;
+            }
+            if !self.pool.is_empty() {
+                c.named_mutation("ops.pool", |ctx| {
+                    let op = ctx.rng().choose(&self.pool).unwrap().clone();
+                    insert(ctx, ops, op)
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
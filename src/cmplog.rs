@@ -0,0 +1,259 @@
+//! Comparison-operand feedback for mutators.
+//!
+//! Programs under test frequently compare their inputs against constants:
+//! magic numbers, lengths, keywords, and so on. Random mutation rarely stumbles
+//! upon these constants by chance. Fuzzers like libFuzzer and AFL++ address
+//! this by instrumenting comparisons (for example, via
+//! `__sanitizer_cov_trace_cmp4` and friends) and feeding the observed operands
+//! back into their mutators. This technique is often called "CmpLog" or
+//! "input-to-state correspondence".
+//!
+//! A [`CmpLog`] is a bounded pool of such observed comparison operands. Every
+//! [`Session`][crate::Session] has one, accessible via
+//! [`Session::cmp_log`][crate::Session::cmp_log]. Record operands into it
+//! while running your program under test, and subsequent mutations will draw
+//! from it: integer mutators will sometimes replace a value that matches one
+//! side of a recorded comparison with the other side, or otherwise replace the
+//! value with a recorded operand of the same width.
+//!
+//! Recorded byte-string operands, as from `memcmp` or `strcmp`, that are
+//! exactly as wide as an integer type (for example, a four-byte magic number)
+//! are likewise spliced into integers of that width, in either byte order. All
+//! recorded byte-string operands are also available to custom mutators via
+//! [`Context::cmp_log`][crate::Context::cmp_log] and [`CmpLog::bytes`].
+//!
+//! # Example
+//!
+//! ```
+//! # fn foo() -> mutatis::Result<()> {
+//! use mutatis::{mutators as m, Session};
+//!
+//! fn parse_header(x: u32) -> bool {
+//!     x == 0x7f454c46
+//! }
+//!
+//! let mut session = Session::new();
+//! let mut value = 0u32;
+//!
+//! let mut found = false;
+//! for _ in 0..1000 {
+//!     session.mutate_with(&mut m::u32(), &mut value)?;
+//!
+//!     // Manually instrument the comparison inside `parse_header`. In a real
+//!     // fuzzer, these operands would instead be collected by the compiler's
+//!     // comparison-tracing instrumentation.
+//!     session.cmp_log().observe_u32(value, 0x7f454c46);
+//!
+//!     if parse_header(value) {
+//!         found = true;
+//!         break;
+//!     }
+//! }
+//!
+//! assert!(found);
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! ```
+
+use alloc::vec::Vec;
+
+/// A bounded pool of observed comparison operands.
+///
+/// Operands of signed integer comparisons should be recorded with the
+/// `observe_u*` method of the same width, after casting them with `as`.
+///
+/// See [the module-level documentation][crate::cmplog] for details and example
+/// usage.
+#[derive(Clone, Debug)]
+pub struct CmpLog {
+    /// Integer operand pairs, indexed by the base-2 logarithm of their width
+    /// in bytes: 1, 2, 4, and 8.
+    ints: [Pool<(u64, u64)>; 4],
+    bytes: Pool<(Vec<u8>, Vec<u8>)>,
+    capacity: usize,
+}
+
+impl Default for CmpLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CmpLog {
+    const DEFAULT_CAPACITY: usize = 256;
+
+    /// Create a new, empty `CmpLog` with the default capacity.
+    ///
+    /// The default capacity is 256 operand pairs of each kind.
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Create a new, empty `CmpLog` that retains at most `capacity` operand
+    /// pairs of each kind: one-, two-, four-, and eight-byte integers, and
+    /// byte strings.
+    ///
+    /// Once full, newly observed operands replace the oldest ones.
+    pub fn with_capacity(capacity: usize) -> Self {
+        CmpLog {
+            ints: Default::default(),
+            bytes: Pool::default(),
+            capacity,
+        }
+    }
+
+    /// Record a comparison between two `u8` operands.
+    pub fn observe_u8(&mut self, a: u8, b: u8) {
+        self.observe_int(0, a.into(), b.into());
+    }
+
+    /// Record a comparison between two `u16` operands.
+    pub fn observe_u16(&mut self, a: u16, b: u16) {
+        self.observe_int(1, a.into(), b.into());
+    }
+
+    /// Record a comparison between two `u32` operands.
+    pub fn observe_u32(&mut self, a: u32, b: u32) {
+        self.observe_int(2, a.into(), b.into());
+    }
+
+    /// Record a comparison between two `u64` operands.
+    pub fn observe_u64(&mut self, a: u64, b: u64) {
+        self.observe_int(3, a, b);
+    }
+
+    fn observe_int(&mut self, width_log2: usize, a: u64, b: u64) {
+        let pool = &mut self.ints[width_log2];
+        if a == b || pool.contains(|e| *e == (a, b)) {
+            return;
+        }
+        pool.push(self.capacity, (a, b));
+    }
+
+    /// Record a comparison between two byte strings, as from `memcmp` or
+    /// `strcmp`.
+    pub fn observe_bytes(&mut self, a: &[u8], b: &[u8]) {
+        if a == b || self.bytes.contains(|(x, y)| x == a && y == b) {
+            return;
+        }
+        self.bytes.push(self.capacity, (a.to_vec(), b.to_vec()));
+    }
+
+    /// The total number of operand pairs recorded in this `CmpLog`.
+    pub fn len(&self) -> usize {
+        self.ints.iter().map(|p| p.entries.len()).sum::<usize>() + self.bytes.entries.len()
+    }
+
+    /// Is this `CmpLog` empty?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all recorded operands.
+    pub fn clear(&mut self) {
+        for pool in &mut self.ints {
+            pool.clear();
+        }
+        self.bytes.clear();
+    }
+
+    /// Iterate over the recorded byte-string operand pairs.
+    pub fn bytes(&self) -> impl ExactSizeIterator<Item = (&[u8], &[u8])> + '_ {
+        self.bytes.entries.iter().map(|(a, b)| (&a[..], &b[..]))
+    }
+
+    /// The recorded integer operand pairs that are `width` bytes wide.
+    fn ints(&self, width: usize) -> &[(u64, u64)] {
+        match width {
+            1 => &self.ints[0].entries,
+            2 => &self.ints[1].entries,
+            4 => &self.ints[2].entries,
+            8 => &self.ints[3].entries,
+            _ => &[],
+        }
+    }
+
+    /// The recorded byte-string operand pairs where both sides are exactly
+    /// `width` bytes long.
+    fn bytes_of_width(&self, width: usize) -> impl Iterator<Item = (&[u8], &[u8])> + '_ {
+        self.bytes()
+            .filter(move |(a, b)| a.len() == width && b.len() == width)
+    }
+
+    /// The number of recorded operand pairs that can be spliced into integers
+    /// that are `width` bytes wide: integer operands of that width, and
+    /// byte-string operands of that length.
+    pub(crate) fn count_of_width(&self, width: usize) -> usize {
+        if !matches!(width, 1 | 2 | 4 | 8) {
+            return 0;
+        }
+        self.ints(width).len() + self.bytes_of_width(width).count()
+    }
+
+    /// Get the `index`th operand pair that can be spliced into integers that
+    /// are `width` bytes wide, interpreting byte-string operands as
+    /// big-endian integers if `big_endian` is true, and little-endian
+    /// otherwise.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub(crate) fn get_of_width(&self, width: usize, index: usize, big_endian: bool) -> (u64, u64) {
+        let ints = self.ints(width);
+        if let Some(pair) = ints.get(index) {
+            return *pair;
+        }
+
+        let to_int = |bytes: &[u8]| {
+            let shift = |acc: u64, b: &u8| acc << 8 | u64::from(*b);
+            if big_endian {
+                bytes.iter().fold(0, shift)
+            } else {
+                bytes.iter().rev().fold(0, shift)
+            }
+        };
+        let (a, b) = self
+            .bytes_of_width(width)
+            .nth(index - ints.len())
+            .expect("operand index out of bounds");
+        (to_int(a), to_int(b))
+    }
+}
+
+/// A fixed-capacity ring buffer.
+#[derive(Clone, Debug)]
+struct Pool<T> {
+    entries: Vec<T>,
+    next: usize,
+}
+
+impl<T> Default for Pool<T> {
+    fn default() -> Self {
+        Pool {
+            entries: Vec::new(),
+            next: 0,
+        }
+    }
+}
+
+impl<T> Pool<T> {
+    fn contains(&self, f: impl FnMut(&T) -> bool) -> bool {
+        self.entries.iter().any(f)
+    }
+
+    fn push(&mut self, capacity: usize, entry: T) {
+        if capacity == 0 {
+            return;
+        }
+        if self.entries.len() < capacity {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.next] = entry;
+            self.next = (self.next + 1) % capacity;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.next = 0;
+    }
+}
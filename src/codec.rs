@@ -0,0 +1,175 @@
+//! Converting between fuzzers' raw bytes and structured values.
+//!
+//! Most fuzzers, including `libfuzzer` and AFL, don't know anything about your
+//! structured types: they just manipulate byte buffers. To mutate a fuzzer's
+//! test case with a `mutatis` mutator, we must first decode the raw bytes into
+//! a structured value, mutate that value, and then encode the mutated value
+//! back into raw bytes for the fuzzer. The [`Codec`] trait captures that
+//! round trip.
+//!
+//! This module also provides a few ready-made codecs:
+//!
+//! * [`RawBytes`]: Use the fuzzer's raw bytes as a `Vec<u8>` directly.
+//!
+//! * [`Bincode`]: Encode any `serde`-serializable type with
+//!   [`bincode`](https://docs.rs/bincode). Requires the `bincode` cargo feature.
+//!
+//! * [`Postcard`]: Encode any `serde`-serializable type with
+//!   [`postcard`](https://docs.rs/postcard). Requires the `postcard` cargo
+//!   feature.
+
+use alloc::vec::Vec;
+
+/// A method of encoding `T` values as raw bytes, and decoding them back again.
+///
+/// A codec's `decode` and `encode` methods should roundtrip: decoding the
+/// result of encoding a value should produce an equivalent value.
+///
+/// # Example
+///
+/// ```
+/// use mutatis::codec::Codec;
+///
+/// /// Encode `u32`s as four little-endian bytes.
+/// struct LittleEndian;
+///
+/// impl Codec<u32> for LittleEndian {
+///     fn decode(bytes: &[u8]) -> Option<u32> {
+///         let bytes = bytes.first_chunk::<4>()?;
+///         Some(u32::from_le_bytes(*bytes))
+///     }
+///
+///     fn encode(value: &u32, bytes: &mut Vec<u8>) {
+///         bytes.extend_from_slice(&value.to_le_bytes());
+///     }
+/// }
+///
+/// let mut bytes = vec![];
+/// LittleEndian::encode(&0x1234_5678, &mut bytes);
+/// assert_eq!(LittleEndian::decode(&bytes), Some(0x1234_5678));
+/// ```
+pub trait Codec<T> {
+    /// Decode a `T` value from the given raw bytes.
+    ///
+    /// Returns `None` if the bytes are not a valid encoding of a `T` value.
+    fn decode(bytes: &[u8]) -> Option<T>;
+
+    /// Encode the given `T` value, appending its raw bytes to `bytes`.
+    fn encode(value: &T, bytes: &mut Vec<u8>);
+}
+
+/// A codec that uses raw bytes as a `Vec<u8>`, without any conversion.
+///
+/// Every sequence of bytes is a valid encoding.
+///
+/// # Example
+///
+/// ```
+/// use mutatis::codec::{Codec, RawBytes};
+///
+/// let bytes = RawBytes::decode(b"hello").unwrap();
+/// assert_eq!(bytes, b"hello");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RawBytes;
+
+impl Codec<Vec<u8>> for RawBytes {
+    fn decode(bytes: &[u8]) -> Option<Vec<u8>> {
+        Some(bytes.to_vec())
+    }
+
+    fn encode(value: &Vec<u8>, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(value);
+    }
+}
+
+/// A codec that encodes `serde`-serializable types with
+/// [`bincode`](https://docs.rs/bincode).
+///
+/// Values are encoded with `bincode`'s default, fixed-width integer encoding,
+/// so this codec is compatible with inputs created via `bincode::serialize`.
+/// When decoding, trailing bytes are ignored and the decoder will not allocate
+/// more memory than the size of the input, so that malformed inputs cannot
+/// cause huge allocations.
+///
+/// # Panics
+///
+/// Encoding panics if `bincode` fails to serialize the value, for example
+/// because the value contains a sequence whose length is not known in advance.
+///
+/// # Example
+///
+/// ```
+/// use mutatis::codec::{Bincode, Codec};
+///
+/// let mut bytes = vec![];
+/// Bincode::encode(&(1_u32, String::from("hello")), &mut bytes);
+///
+/// let value: (u32, String) = Bincode::decode(&bytes).unwrap();
+/// assert_eq!(value, (1, String::from("hello")));
+/// ```
+#[cfg(feature = "bincode")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bincode;
+
+#[cfg(feature = "bincode")]
+impl<T> Codec<T> for Bincode
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn decode(bytes: &[u8]) -> Option<T> {
+        use bincode::Options;
+        bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .with_limit(u64::try_from(bytes.len()).unwrap())
+            .deserialize(bytes)
+            .ok()
+    }
+
+    fn encode(value: &T, bytes: &mut Vec<u8>) {
+        bincode::serialize_into(bytes, value)
+            .unwrap_or_else(|e| panic!("failed to encode value with bincode: {e}"));
+    }
+}
+
+/// A codec that encodes `serde`-serializable types with
+/// [`postcard`](https://docs.rs/postcard).
+///
+/// Postcard's encoding is compact, which tends to keep fuzzers' inputs small.
+///
+/// # Panics
+///
+/// Encoding panics if `postcard` fails to serialize the value, for example
+/// because the value contains a sequence whose length is not known in advance.
+///
+/// # Example
+///
+/// ```
+/// use mutatis::codec::{Codec, Postcard};
+///
+/// let mut bytes = vec![];
+/// Postcard::encode(&(1_u32, String::from("hello")), &mut bytes);
+///
+/// let value: (u32, String) = Postcard::decode(&bytes).unwrap();
+/// assert_eq!(value, (1, String::from("hello")));
+/// ```
+#[cfg(feature = "postcard")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Postcard;
+
+#[cfg(feature = "postcard")]
+impl<T> Codec<T> for Postcard
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn decode(bytes: &[u8]) -> Option<T> {
+        postcard::from_bytes(bytes).ok()
+    }
+
+    fn encode(value: &T, bytes: &mut Vec<u8>) {
+        let buf = core::mem::take(bytes);
+        *bytes = postcard::to_extend(value, buf)
+            .unwrap_or_else(|e| panic!("failed to encode value with postcard: {e}"));
+    }
+}
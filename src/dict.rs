@@ -0,0 +1,225 @@
+//! Dictionaries of tokens for mutators to splice into values.
+//!
+//! Fuzzers benefit greatly from dictionaries of "magic" values: keywords,
+//! magic numbers, protocol tokens, and so on, that are unlikely to be produced
+//! by random mutation alone. A [`Dict`] is a collection of such tokens, as raw
+//! bytes. Registering a `Dict` with a [`Session`][crate::Session], via
+//! [`Session::dict`][crate::Session::dict], makes the built-in mutators
+//! consult it: for example, integer mutators will sometimes replace a value
+//! with a dictionary token of the same width.
+//!
+//! Dictionaries can be parsed from the `.dict` file format used by libFuzzer
+//! and AFL, which looks like this:
+//!
+//! ```text
+//! # Lines starting with `#` are comments.
+//! kw_select="SELECT"
+//! magic="\x7fELF"
+//! "unnamed tokens are allowed too"
+//! ```
+//!
+//! To mutate values of any type by choosing from a fixed set of values,
+//! rather than splicing raw byte tokens, see the
+//! [`dictionary`][crate::mutators::dictionary] mutator instead.
+
+use crate::{Error, Result};
+use alloc::{collections::BTreeMap, format, vec::Vec};
+
+/// A dictionary of byte tokens.
+///
+/// See [the module-level documentation][crate::dict] for details.
+///
+/// # Example
+///
+/// ```
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{dict::Dict, Session};
+///
+/// let dict = Dict::parse(r#"
+///     ## Some magic numbers.
+///     magic="\xca\xfe\xba\xbe"
+///     version="\x01\x00"
+/// "#)?;
+/// assert_eq!(dict.len(), 2);
+///
+/// let mut session = Session::new().dict(dict);
+///
+/// let mut x = 0u32;
+/// for _ in 0..10 {
+///     session.mutate(&mut x)?;
+///     println!("mutated x is {x:#x}");
+/// }
+///
+/// // Example output:
+/// //
+/// //     mutated x is 0x5ad1a0b7
+/// //     mutated x is 0xcafebabe
+/// //     mutated x is 0x1d0b8a2
+/// //     mutated x is 0xbebafeca
+/// //     ...
+/// # Ok(())
+/// # }
+/// # foo().unwrap()
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Dict {
+    tokens: Vec<Vec<u8>>,
+    by_len: BTreeMap<usize, Vec<usize>>,
+}
+
+impl Dict {
+    /// Create a new, empty dictionary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a token into this dictionary.
+    ///
+    /// Empty tokens and duplicates are ignored.
+    pub fn insert(&mut self, token: impl Into<Vec<u8>>) {
+        let token = token.into();
+        if token.is_empty() {
+            return;
+        }
+
+        let same_len = self.by_len.entry(token.len()).or_default();
+        if same_len.iter().any(|i| self.tokens[*i] == token) {
+            return;
+        }
+
+        same_len.push(self.tokens.len());
+        self.tokens.push(token);
+    }
+
+    /// The number of tokens in this dictionary.
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Is this dictionary empty?
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Iterate over the tokens in this dictionary.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &[u8]> + '_ {
+        self.tokens.iter().map(|t| &t[..])
+    }
+
+    /// The number of tokens in this dictionary that are exactly `len` bytes
+    /// long.
+    pub(crate) fn count_of_len(&self, len: usize) -> usize {
+        self.by_len.get(&len).map_or(0, |ts| ts.len())
+    }
+
+    /// Get the `index`th token that is exactly `len` bytes long.
+    pub(crate) fn get_of_len(&self, len: usize, index: usize) -> &[u8] {
+        &self.tokens[self.by_len[&len][index]]
+    }
+
+    /// Parse a dictionary from the libFuzzer/AFL `.dict` file format.
+    ///
+    /// Each non-empty line that does not start with `#` must contain one
+    /// token: a double-quoted string, optionally preceded by a name and an
+    /// `=`, as in `name="token"`. Within the quotes, `\\`, `\"`, and `\xNN`
+    /// escapes are supported; all other characters stand for themselves.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut dict = Dict::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let token = parse_entry(line)
+                .map_err(|e| Error::other(format!("invalid dictionary line {}: {e}", i + 1)))?;
+            dict.insert(token);
+        }
+        Ok(dict)
+    }
+
+    /// Read and parse a dictionary from a libFuzzer/AFL `.dict` file.
+    ///
+    /// See [`Dict::parse`] for details on the file format.
+    #[cfg(feature = "std")]
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            Error::other(format!(
+                "failed to read dictionary file {}: {e}",
+                path.display()
+            ))
+        })?;
+        Self::parse(&text)
+    }
+}
+
+impl<T> Extend<T> for Dict
+where
+    T: Into<Vec<u8>>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for token in iter {
+            self.insert(token);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Dict
+where
+    T: Into<Vec<u8>>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut dict = Dict::new();
+        dict.extend(iter);
+        dict
+    }
+}
+
+/// Parse a single `name="token"` or `"token"` dictionary entry.
+fn parse_entry(line: &str) -> core::result::Result<Vec<u8>, &'static str> {
+    let quote = line.find('"').ok_or("expected a double-quoted token")?;
+
+    let name = line[..quote].trim_end();
+    if !name.is_empty() {
+        let name = name.strip_suffix('=').ok_or("expected `=` after name")?;
+        let name = name.trim_end();
+        // AFL allows a `@<level>` suffix on names.
+        let name = name.split_once('@').map_or(name, |(n, _)| n);
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err("invalid name");
+        }
+    }
+
+    let quoted = &line[quote + 1..];
+    let mut token = Vec::new();
+    let mut bytes = quoted.bytes();
+    loop {
+        match bytes.next().ok_or("unterminated token")? {
+            b'"' => break,
+            b'\\' => match bytes.next().ok_or("unterminated escape")? {
+                b'\\' => token.push(b'\\'),
+                b'"' => token.push(b'"'),
+                b'x' => {
+                    let hi = bytes.next().and_then(hex_digit);
+                    let lo = bytes.next().and_then(hex_digit);
+                    match (hi, lo) {
+                        (Some(hi), Some(lo)) => token.push(hi << 4 | lo),
+                        _ => return Err("invalid `\\x` escape"),
+                    }
+                }
+                _ => return Err("invalid escape"),
+            },
+            b => token.push(b),
+        }
+    }
+
+    if bytes.any(|b| !b.is_ascii_whitespace()) {
+        return Err("unexpected characters after token");
+    }
+
+    Ok(token)
+}
+
+fn hex_digit(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
@@ -12,19 +12,47 @@ extern crate alloc;
 extern crate std;
 
 pub mod _guide;
+#[cfg(feature = "alloc")]
+pub mod cmplog;
+#[cfg(feature = "alloc")]
+pub mod codec;
+#[cfg(feature = "alloc")]
+pub mod dict;
 pub mod error;
 mod log;
 pub mod mutators;
 mod rng;
+#[cfg(feature = "alloc")]
+mod schedule;
+mod size_hint;
 
 use core::ops;
 
 pub use error::{Error, Result};
 pub use rng::Rng;
+pub use size_hint::SizeHint;
+
+#[cfg(feature = "aflpp")]
+pub mod aflpp;
 
 #[cfg(feature = "check")]
 pub mod check;
 
+#[cfg(feature = "libafl")]
+pub mod libafl;
+
+#[cfg(feature = "libfuzzer")]
+pub mod libfuzzer;
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+#[cfg(feature = "quickcheck")]
+pub mod quickcheck;
+
+#[cfg(feature = "std")]
+pub mod reduce;
+
 #[cfg(feature = "derive")]
 /// Automatically derive a mutator for a type.
 ///
@@ -32,6 +60,50 @@ pub mod check;
 /// guide][crate::_guide::derive_macro] for details.
 pub use mutatis_derive::Mutate;
 
+#[cfg(all(feature = "check", feature = "derive"))]
+/// Turn a property function into a `#[test]` that is checked with
+/// [`mutatis::check::Check`][crate::check::Check].
+///
+/// The property's arguments must be shared references (or `Clone` values)
+/// whose types implement [`DefaultMutate`], and it must return either `()` or
+/// a `Result<(), S>` where `S: ToString`. Properties with multiple arguments
+/// are checked as a tuple of those arguments. When the check finds a failing
+/// input, the test panics with the (shrunken) input and the failure message.
+///
+/// The attribute accepts the following optional arguments:
+///
+/// * `iters = <expr>`: the number of check iterations, see
+///   [`Check::iters`][crate::check::Check::iters].
+///
+/// * `shrink_iters = <expr>`: the number of shrink iterations, see
+///   [`Check::shrink_iters`][crate::check::Check::shrink_iters].
+///
+/// * `corpus = <expr>`: the initial corpus, which must be an `IntoIterator` of
+///   the argument type (or of a tuple of the argument types when there are
+///   multiple arguments). Defaults to a single `Default::default()` value.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(all(feature = "check", feature = "derive"))]
+/// # mod example {
+/// #[mutatis::check(iters = 500, corpus = [(0, 1), (u32::MAX, 2)])]
+/// fn division_round_trips(x: &u32, y: &u32) -> Result<(), String> {
+///     if *y == 0 {
+///         return Ok(());
+///     }
+///     let q = x / y;
+///     let r = x % y;
+///     if q * y + r == *x {
+///         Ok(())
+///     } else {
+///         Err(format!("{q} * {y} + {r} != {x}"))
+///     }
+/// }
+/// # }
+/// ```
+pub use mutatis_derive::check;
+
 /// A mutation session and its configuration.
 ///
 /// This type allows you to configure things like setting the RNG seed, or
@@ -85,6 +157,16 @@ impl Session {
             context: Context {
                 rng: Rng::default(),
                 shrink: false,
+                depth: 0,
+                max_depth: Context::DEFAULT_MAX_DEPTH,
+                size: 0,
+                max_size: None,
+                #[cfg(feature = "alloc")]
+                dict: dict::Dict::new(),
+                #[cfg(feature = "alloc")]
+                cmp_log: cmplog::CmpLog::new(),
+                #[cfg(feature = "alloc")]
+                scheduler: None,
             },
         }
     }
@@ -103,6 +185,149 @@ impl Session {
         self
     }
 
+    /// Set the maximum depth of nested recursive mutators, such as
+    /// [`lazy`][crate::mutators::lazy] and
+    /// [`recursive`][crate::mutators::recursive] mutators, and the default
+    /// mutators for `Box<T>` values.
+    ///
+    /// Recursive mutators do not register any candidate mutations, and fail to
+    /// generate new values, when they are nested this deeply. This ensures
+    /// that generating values of recursive types terminates, and bounds how
+    /// deep into a recursive value mutations are applied.
+    ///
+    /// Defaults to `32`.
+    pub fn max_depth(mut self, max_depth: u32) -> Self {
+        self.context.max_depth = max_depth;
+        self
+    }
+
+    /// Set the maximum size, in bytes, that values may grow to.
+    ///
+    /// Mutators do not register candidate mutations that grow a value (for
+    /// example by inserting or generating new sub-values) when doing so would
+    /// exceed this budget. Generators likewise avoid generating values that
+    /// are larger than the remaining budget, where possible.
+    ///
+    /// The size of the value being mutated is only known when using the
+    /// [`mutate_sized`][Session::mutate_sized] and
+    /// [`mutate_sized_with`][Session::mutate_sized_with] methods, which
+    /// estimate it with the [`SizeHint`] trait. Otherwise it is assumed to be
+    /// zero, and only the size of each individual growth mutation is checked
+    /// against the budget.
+    ///
+    /// Sizes are estimates, so this is a best-effort bound rather than a
+    /// guarantee. Fuzzer integrations should still check the final size of
+    /// each mutated test case.
+    ///
+    /// Defaults to no maximum size.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn foo() -> mutatis::Result<()> {
+    /// use mutatis::Session;
+    ///
+    /// let mut session = Session::new().max_size(8);
+    ///
+    /// // This value is already at the maximum size, so it can never become
+    /// // `Some`.
+    /// let mut value: (u64, Option<u32>) = (0, None);
+    /// for _ in 0..10 {
+    ///     session.mutate_sized(&mut value)?;
+    ///     assert!(value.1.is_none());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.context.max_size = Some(max_size);
+        self
+    }
+
+    /// Register a dictionary of tokens with this session.
+    ///
+    /// The built-in mutators consult the session's dictionary to splice in
+    /// registered tokens. For example, integer mutators will sometimes replace
+    /// a value with a dictionary token of the same width, interpreted as
+    /// either a little- or big-endian integer.
+    ///
+    /// See the [`dict`] module for details and example usage.
+    ///
+    /// Defaults to an empty dictionary.
+    #[cfg(feature = "alloc")]
+    pub fn dict(mut self, dict: dict::Dict) -> Self {
+        self.context.dict = dict;
+        self
+    }
+
+    /// Get this session's pool of observed comparison operands.
+    ///
+    /// Record the operands of comparisons performed by your program under test
+    /// into this pool, and the built-in mutators will draw from them in
+    /// subsequent mutations. For example, integer mutators will sometimes
+    /// replace a value that matches one operand of a recorded comparison with
+    /// the other operand.
+    ///
+    /// See the [`cmplog`] module for details and example usage.
+    #[cfg(feature = "alloc")]
+    pub fn cmp_log(&mut self) -> &mut cmplog::CmpLog {
+        &mut self.context.cmp_log
+    }
+
+    /// Set whether to adaptively schedule candidate mutations based on
+    /// feedback or not.
+    ///
+    /// By default, every candidate mutation is equally likely to be chosen.
+    /// When adaptive scheduling is enabled, call
+    /// [`feedback`][Session::feedback] after executing each mutated input to
+    /// report whether it was interesting (for example, because it discovered
+    /// new code coverage). The session then tracks how often the candidates
+    /// registered under each name, via [`Candidates::named_mutation`], lead
+    /// to interesting inputs, and chooses more successful kinds of mutations
+    /// more often, and less successful ones less often. Unnamed candidates
+    /// are always given the average weight. All of the built-in mutators name
+    /// their candidates.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn foo() -> mutatis::Result<()> {
+    /// use mutatis::Session;
+    ///
+    /// # fn run_and_check_coverage(_: u64) -> bool { false }
+    /// let mut session = Session::new().adaptive(true);
+    ///
+    /// let mut input = 0u64;
+    /// for _ in 0..100 {
+    ///     session.mutate(&mut input)?;
+    ///     let new_coverage = run_and_check_coverage(input);
+    ///     session.feedback(new_coverage);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap()
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn adaptive(mut self, adaptive: bool) -> Self {
+        self.context.scheduler = adaptive.then(schedule::Scheduler::default);
+        self
+    }
+
+    /// Report whether the input produced by the most recent mutation was
+    /// interesting or not.
+    ///
+    /// This has no effect unless [adaptive scheduling][Session::adaptive] is
+    /// enabled.
+    #[cfg(feature = "alloc")]
+    pub fn feedback(&mut self, interesting: bool) {
+        if let Some(scheduler) = &mut self.context.scheduler {
+            scheduler.feedback(interesting);
+        }
+    }
+
     /// Mutate the given `value` with its default mutator and within the
     /// constraints of this `Session`'s configuration.
     ///
@@ -142,6 +367,7 @@ impl Session {
     where
         T: DefaultMutate,
     {
+        self.context.begin_mutation();
         self.context.mutate(value)
     }
 
@@ -182,8 +408,37 @@ impl Session {
     /// # foo().unwrap();
     /// ```
     pub fn mutate_with<T>(&mut self, mutator: &mut impl Mutate<T>, value: &mut T) -> Result<()> {
+        self.context.begin_mutation();
         self.context.mutate_with(mutator, value)
     }
+
+    /// Like [`mutate`][Session::mutate], but estimates the size of `value` with
+    /// its [`SizeHint`] implementation, so that mutations do not grow it beyond
+    /// this session's [maximum size][Session::max_size].
+    pub fn mutate_sized<T>(&mut self, value: &mut T) -> Result<()>
+    where
+        T: DefaultMutate + SizeHint,
+    {
+        let mut mutator = mutators::default::<T>();
+        self.mutate_sized_with(&mut mutator, value)
+    }
+
+    /// Like [`mutate_with`][Session::mutate_with], but estimates the size of
+    /// `value` with its [`SizeHint`] implementation, so that mutations do not
+    /// grow it beyond this session's [maximum size][Session::max_size].
+    pub fn mutate_sized_with<T>(
+        &mut self,
+        mutator: &mut impl Mutate<T>,
+        value: &mut T,
+    ) -> Result<()>
+    where
+        T: SizeHint,
+    {
+        let size = value.size_hint();
+        self.context.begin_mutation();
+        self.context
+            .with_size(size, |ctx| ctx.mutate_with(mutator, value))
+    }
 }
 
 /// The context for the current mutation.
@@ -201,9 +456,25 @@ impl Session {
 pub struct Context {
     rng: Rng,
     shrink: bool,
+    depth: u32,
+    max_depth: u32,
+    size: usize,
+    max_size: Option<usize>,
+    #[cfg(feature = "alloc")]
+    dict: dict::Dict,
+    #[cfg(feature = "alloc")]
+    cmp_log: cmplog::CmpLog,
+    #[cfg(feature = "alloc")]
+    scheduler: Option<schedule::Scheduler>,
 }
 
 impl Context {
+    const DEFAULT_MAX_DEPTH: u32 = 32;
+
+    /// The number of candidate mutations to try applying before giving up,
+    /// when the chosen candidates turn out to be exhausted.
+    const MAX_APPLY_ATTEMPTS: usize = 8;
+
     /// Get this context's random number generator.
     #[inline]
     #[must_use]
@@ -222,6 +493,177 @@ impl Context {
         self.shrink
     }
 
+    /// The current depth of nested recursive mutators.
+    ///
+    /// This starts at zero, and is incremented every time a recursive mutator
+    /// (such as [`lazy`][crate::mutators::lazy] or
+    /// [`recursive`][crate::mutators::recursive]) delegates to its inner
+    /// mutator.
+    #[inline]
+    #[must_use]
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// The maximum depth of nested recursive mutators, as configured by
+    /// [`Session::max_depth`].
+    ///
+    /// Hand-written `Generate` implementations for recursive types can check
+    /// whether `depth() >= max_depth()` to decide to generate a leaf value.
+    #[inline]
+    #[must_use]
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth
+    }
+
+    /// The maximum size, in bytes, that values may grow to, as configured by
+    /// [`Session::max_size`].
+    #[inline]
+    #[must_use]
+    pub fn max_size(&self) -> Option<usize> {
+        self.max_size
+    }
+
+    /// The estimated size, in bytes, of the value being mutated, or zero if it
+    /// is unknown.
+    ///
+    /// See [`Session::mutate_sized_with`] for details.
+    #[inline]
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// How many more bytes the value being mutated may grow by before
+    /// exceeding the [maximum size][Session::max_size], or `None` if there is
+    /// no maximum size.
+    #[inline]
+    #[must_use]
+    pub fn remaining_size(&self) -> Option<usize> {
+        self.max_size.map(|max| max.saturating_sub(self.size))
+    }
+
+    /// Whether the value being mutated may grow by `additional` bytes without
+    /// exceeding the [maximum size][Session::max_size].
+    ///
+    /// Mutator implementations should not register candidate mutations that
+    /// grow a value when this returns `false`.
+    #[inline]
+    #[must_use]
+    pub fn can_grow(&self, additional: usize) -> bool {
+        self.remaining_size().map_or(true, |r| additional <= r)
+    }
+
+    /// The dictionary of tokens registered with this context's session, as
+    /// configured by [`Session::dict`].
+    ///
+    /// Mutator implementations may consult this dictionary to splice in
+    /// registered tokens.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn dict(&self) -> &dict::Dict {
+        &self.dict
+    }
+
+    /// Choose a random dictionary token that is exactly `N` bytes long, if
+    /// any.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn choose_token<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let index = self.rng.gen_index(self.dict.count_of_len(N))?;
+        let token = self.dict.get_of_len(N, index);
+        Some(token.try_into().unwrap())
+    }
+
+    /// The pool of observed comparison operands registered with this
+    /// context's session, as accessed by [`Session::cmp_log`].
+    ///
+    /// Mutator implementations may draw from this pool to produce values that
+    /// satisfy comparisons performed by the program under test.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn cmp_log(&self) -> &cmplog::CmpLog {
+        &self.cmp_log
+    }
+
+    /// Choose a random comparison operand that is `width` bytes wide, if any.
+    ///
+    /// If `current` (truncated to `width` bytes) matches one side of the
+    /// chosen comparison, then the other side is returned. Otherwise, a random
+    /// side is returned.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn choose_cmp_operand(&mut self, width: usize, current: u64) -> Option<u64> {
+        let index = self.rng.gen_index(self.cmp_log.count_of_width(width))?;
+        let big_endian = self.rng.gen_bool();
+        let (a, b) = self.cmp_log.get_of_width(width, index, big_endian);
+        let current = if width < 8 {
+            current & ((1 << (width * 8)) - 1)
+        } else {
+            current
+        };
+        Some(if current == a {
+            b
+        } else if current == b || self.rng.gen_bool() {
+            a
+        } else {
+            b
+        })
+    }
+
+    /// Start a new top-level mutation, forgetting which candidates were
+    /// applied during the previous one.
+    #[inline]
+    fn begin_mutation(&mut self) {
+        #[cfg(feature = "alloc")]
+        if let Some(scheduler) = &mut self.scheduler {
+            scheduler.begin();
+        }
+    }
+
+    /// The weight of a candidate mutation with the given name.
+    #[inline]
+    fn candidate_weight(&self, name: Option<&'static str>) -> u32 {
+        #[cfg(feature = "alloc")]
+        if let Some(scheduler) = &self.scheduler {
+            return scheduler.weight(name);
+        }
+        let _ = name;
+        1
+    }
+
+    /// Record that a candidate mutation with the given name was applied.
+    #[inline]
+    fn applied_candidate(&mut self, name: Option<&'static str>) {
+        #[cfg(feature = "alloc")]
+        if let (Some(scheduler), Some(name)) = (&mut self.scheduler, name) {
+            scheduler.applied(name);
+        }
+        let _ = name;
+    }
+
+    /// Run `f` with the size of the value being mutated set to `size`.
+    pub(crate) fn with_size<R>(&mut self, size: usize, f: impl FnOnce(&mut Context) -> R) -> R {
+        let old = core::mem::replace(&mut self.size, size);
+        let result = f(self);
+        self.size = old;
+        result
+    }
+
+    /// Run `f` one level deeper in the recursive mutator nesting, or return
+    /// `None` if we are already at the maximum depth.
+    #[inline]
+    pub(crate) fn nested<R>(&mut self, f: impl FnOnce(&mut Context) -> R) -> Option<R> {
+        if self.depth >= self.max_depth {
+            log::trace!("reached maximum depth of {}", self.max_depth);
+            return None;
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        Some(result)
+    }
+
     #[inline]
     pub(crate) fn mutate<T>(&mut self, value: &mut T) -> Result<()>
     where
@@ -240,6 +682,35 @@ impl Context {
         self.choose_and_apply_mutation(value, |c, value| mutator.mutate(c, value))
     }
 
+    /// Count the number of candidate mutations that `mutator` registers for
+    /// `value`, ignoring their weights.
+    #[cfg(feature = "std")]
+    pub(crate) fn count_candidates_with<T>(
+        &mut self,
+        mutator: &mut impl Mutate<T>,
+        value: &mut T,
+    ) -> Result<usize> {
+        self.count_mutations(value, Weighting::Unweighted, |c, value| {
+            mutator.mutate(c, value)
+        })
+    }
+
+    /// Apply the `target`th candidate mutation that `mutator` registers for
+    /// `value`, where `count` is the result of a previous call to
+    /// `count_candidates_with` for the same mutator and value.
+    #[cfg(feature = "std")]
+    pub(crate) fn apply_candidate_with<T>(
+        &mut self,
+        mutator: &mut impl Mutate<T>,
+        value: &mut T,
+        target: usize,
+        count: usize,
+    ) -> Result<()> {
+        self.apply_mutation(value, target, count, Weighting::Unweighted, |c, value| {
+            mutator.mutate(c, value)
+        })
+    }
+
     fn choose_and_apply_mutation<T>(
         &mut self,
         value: &mut T,
@@ -248,40 +719,90 @@ impl Context {
         log::trace!("=== choosing an applying a mutation ===");
 
         // Count how many mutations we *could* perform.
-        let mut candidates = Candidates {
-            context: self,
-            phase: Phase::Count(0),
-            applied_mutation: false,
-        };
-        mutate_impl(&mut candidates, value)?;
-
-        let count = match candidates.phase {
-            Phase::Count(count) => usize::try_from(count).unwrap(),
-            Phase::Mutate { .. } => unreachable!(),
-        };
-        log::trace!("counted {count} mutations");
+        let (count, registered) =
+            self.count_weighted_mutations(value, Weighting::Weighted, &mut mutate_impl)?;
+        log::trace!("counted {count} mutations from {registered} candidates");
 
         if count == 0 {
             log::trace!("mutator exhausted");
             return Err(Error::exhausted());
         }
 
-        // Choose a random target mutation to actually perform.
-        let target = candidates.context.rng().gen_index(count).unwrap();
-        log::trace!("targeting mutation {target}");
-        debug_assert!(target < count);
+        // Choose a random target mutation to actually perform. Some candidates
+        // only discover that they cannot be applied when they are applied, for
+        // example because the value they generated would exceed the maximum
+        // size, in which case they report exhaustion without changing the
+        // value. Try a few other targets before giving up, unless there are no
+        // other candidates to try.
+        for _ in 0..Self::MAX_APPLY_ATTEMPTS {
+            let target = self.rng().gen_index(count).unwrap();
+            log::trace!("targeting mutation {target}");
+            debug_assert!(target < count);
 
-        // Perform the chosen target mutation.
-        candidates.phase = Phase::Mutate {
+            match self.apply_mutation(value, target, count, Weighting::Weighted, &mut mutate_impl) {
+                Err(e) if e.is_exhausted() && registered > 1 => continue,
+                result => return result,
+            }
+        }
+        Err(Error::exhausted())
+    }
+
+    fn count_mutations<T>(
+        &mut self,
+        value: &mut T,
+        weighting: Weighting,
+        mutate_impl: impl FnMut(&mut Candidates, &mut T) -> Result<()>,
+    ) -> Result<usize> {
+        let (count, _registered) = self.count_weighted_mutations(value, weighting, mutate_impl)?;
+        Ok(count)
+    }
+
+    /// Count the total weight of the candidate mutations that `mutate_impl`
+    /// registers, along with the number of distinct candidates registered.
+    fn count_weighted_mutations<T>(
+        &mut self,
+        value: &mut T,
+        weighting: Weighting,
+        mut mutate_impl: impl FnMut(&mut Candidates, &mut T) -> Result<()>,
+    ) -> Result<(usize, usize)> {
+        let mut candidates = Candidates::new(self, Phase::Count(0), weighting);
+        mutate_impl(&mut candidates, value)?;
+
+        match candidates.phase {
+            Phase::Count(count) => {
+                let count = usize::try_from(count)
+                    .map_err(|_| Error::other("too many candidate mutations to choose from"))?;
+                Ok((count, candidates.registered))
+            }
+            Phase::Mutate { .. } => unreachable!(),
+        }
+    }
+
+    fn apply_mutation<T>(
+        &mut self,
+        value: &mut T,
+        target: usize,
+        count: usize,
+        weighting: Weighting,
+        mut mutate_impl: impl FnMut(&mut Candidates, &mut T) -> Result<()>,
+    ) -> Result<()> {
+        debug_assert!(target < count);
+        let phase = Phase::Mutate {
             current: 0,
-            target: u32::try_from(target).unwrap(),
+            target: u64::try_from(target).unwrap(),
         };
+        let mut candidates = Candidates::new(self, phase, weighting);
         match mutate_impl(&mut candidates, value) {
             Err(e) if e.is_early_exit() => {
                 log::trace!("mutation applied successfully");
                 Ok(())
             }
 
+            Err(e) if e.is_exhausted() => {
+                log::trace!("mutator exhausted");
+                Err(e)
+            }
+
             Err(e) => {
                 log::error!("failed to apply mutation: {e}");
                 Err(e)
@@ -331,8 +852,27 @@ impl Context {
 
 #[derive(Clone, Copy)]
 enum Phase {
-    Count(u32),
-    Mutate { current: u32, target: u32 },
+    Count(u64),
+    Mutate { current: u64, target: u64 },
+}
+
+/// How candidate mutations are weighted when enumerating them.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Weighting {
+    /// Weight each candidate by its scheduled weight, scaled by the weights of
+    /// the groups that it belongs to.
+    Weighted,
+
+    /// Like `Weighted`, but register each group of candidates as a single
+    /// candidate with the group's weight, without enumerating its candidates.
+    /// This is used to cheaply estimate the total weight of a group's
+    /// candidates.
+    Estimate,
+
+    /// Every candidate has a weight of one, so that each candidate can be
+    /// enumerated and applied in turn.
+    #[cfg(feature = "std")]
+    Unweighted,
 }
 
 /// The set of mutations that can be applied to a value.
@@ -345,9 +885,32 @@ pub struct Candidates<'a> {
     context: &'a mut Context,
     phase: Phase,
     applied_mutation: bool,
+    registered: usize,
+    probing: bool,
+    weighting: Weighting,
+    scale: f64,
+    rounding_error: f64,
 }
 
 impl<'a> Candidates<'a> {
+    /// The factor by which every candidate's weight is scaled, so that
+    /// [groups][Candidates::weighted_group] can divide their weight among
+    /// their candidates with reasonable precision.
+    const WEIGHT_SCALE: f64 = 256.0;
+
+    fn new(context: &'a mut Context, phase: Phase, weighting: Weighting) -> Self {
+        Candidates {
+            context,
+            phase,
+            applied_mutation: false,
+            registered: 0,
+            probing: false,
+            weighting,
+            scale: Self::WEIGHT_SCALE,
+            rounding_error: 0.0,
+        }
+    }
+
     /// Register a candidate mutation that can be applied to a value.
     ///
     /// This method is called by [`Mutate::mutate`] implementations to register
@@ -360,10 +923,70 @@ impl<'a> Candidates<'a> {
     /// See the [`Mutate::mutate`] trait method documentation for more
     /// information on this method's use.
     #[inline]
-    pub fn mutation(&mut self, mut f: impl FnMut(&mut Context) -> Result<()>) -> Result<()> {
+    pub fn mutation(&mut self, f: impl FnMut(&mut Context) -> Result<()>) -> Result<()> {
+        self.candidate(None, f)
+    }
+
+    /// Register a named candidate mutation that can be applied to a value.
+    ///
+    /// This is like [`Candidates::mutation`], but additionally associates the
+    /// candidate with a `name` describing its kind of mutation, for example
+    /// `"int.dict"` or `"option.none"`. When
+    /// [adaptive scheduling][Session::adaptive] is enabled, candidates are
+    /// chosen with a probability based on how often past candidates with the
+    /// same name led to interesting inputs.
+    #[inline]
+    pub fn named_mutation(
+        &mut self,
+        name: &'static str,
+        f: impl FnMut(&mut Context) -> Result<()>,
+    ) -> Result<()> {
+        self.candidate(Some(name), f)
+    }
+
+    #[inline]
+    fn candidate(
+        &mut self,
+        name: Option<&'static str>,
+        mut f: impl FnMut(&mut Context) -> Result<()>,
+    ) -> Result<()> {
+        let weight = self.context.candidate_weight(name);
+        self.weighted_mutation(weight, |ctx| {
+            ctx.applied_candidate(name);
+            f(ctx)
+        })
+    }
+
+    /// Register a candidate mutation that is `weight` times as likely to be
+    /// chosen as a candidate registered with [`Candidates::mutation`].
+    ///
+    /// A `weight` of zero registers nothing.
+    #[inline]
+    pub(crate) fn weighted_mutation(
+        &mut self,
+        weight: u32,
+        mut f: impl FnMut(&mut Context) -> Result<()>,
+    ) -> Result<()> {
+        let weight = match self.weighting {
+            _ if weight == 0 => return Ok(()),
+            // Round down, but never hide a candidate entirely, and carry the
+            // rounding error over to the next candidate, so that groups of
+            // candidates have close to their intended total weight.
+            Weighting::Weighted | Weighting::Estimate => {
+                let exact = f64::from(weight) * self.scale + self.rounding_error;
+                let rounded = (exact as u64).max(1);
+                self.rounding_error = exact - rounded as f64;
+                rounded
+            }
+            #[cfg(feature = "std")]
+            Weighting::Unweighted => 1,
+        };
         match &mut self.phase {
             Phase::Count(count) => {
-                *count += 1;
+                *count = count
+                    .checked_add(weight)
+                    .ok_or_else(|| Error::other("too many candidate mutations to choose from"))?;
+                self.registered += 1;
                 Ok(())
             }
             Phase::Mutate { current, target } => {
@@ -372,18 +995,105 @@ impl<'a> Candidates<'a> {
                     "{current} <= {target}; did you forget to `?`-propagate the \
                      result of a `Candidates::mutation` call?",
                 );
-                if *current == *target {
+                if *target - *current < weight {
+                    if self.probing {
+                        return Err(Error::early_exit());
+                    }
                     self.applied_mutation = true;
-                    f(&mut self.context)?;
+                    f(self.context)?;
                     Err(Error::early_exit())
                 } else {
-                    *current += 1;
+                    *current += weight;
                     Ok(())
                 }
             }
         }
     }
 
+    /// Register the group of candidate mutations that `f` registers, scaling
+    /// their weights so that, together, they are `weight` times as likely to
+    /// be chosen as a single candidate registered with
+    /// [`Candidates::mutation`], no matter how many candidates there are.
+    ///
+    /// `f` may be called multiple times and must register the same candidates
+    /// each time. A `weight` of zero registers nothing.
+    pub(crate) fn weighted_group(
+        &mut self,
+        weight: u32,
+        mut f: impl FnMut(&mut Candidates) -> Result<()>,
+    ) -> Result<()> {
+        if weight == 0 {
+            return Ok(());
+        }
+        let weight = weight.saturating_mul(self.context.candidate_weight(None));
+
+        match self.weighting {
+            Weighting::Weighted => {}
+
+            // Estimating the total weight of an enclosing group, so assume that
+            // this group registers at least one candidate rather than
+            // enumerating them. This keeps the cost of nested groups linear
+            // rather than exponential in their nesting depth.
+            Weighting::Estimate => return self.weighted_mutation(weight, |_| unreachable!()),
+
+            #[cfg(feature = "std")]
+            Weighting::Unweighted => return f(self),
+        }
+
+        // Estimate the total weight of the group's candidates at the default
+        // scale, and then scale them so that they sum to `weight` candidates
+        // at our current scale. Rounding means that they might not sum to
+        // exactly that, but we do not care about exactness, only that we
+        // register the same weights when counting and when applying mutations.
+        let total = self
+            .context
+            .count_mutations(&mut (), Weighting::Estimate, |c, ()| f(c))?;
+        if total == 0 {
+            return Ok(());
+        }
+
+        let old_scale = self.scale;
+        self.scale = old_scale * f64::from(weight) * Self::WEIGHT_SCALE / total as f64;
+        let result = f(self);
+        self.scale = old_scale;
+        result
+    }
+
+    /// Whether the candidate that will be applied is one of the candidates that
+    /// `f` registers, without applying it.
+    ///
+    /// If it is, the traversal is rewound so that the caller can register
+    /// those same candidates again to actually apply it. Otherwise, they are
+    /// registered as usual and `false` is returned. This lets combinators do
+    /// work, such as snapshotting the value, only when one of their own
+    /// candidates is chosen.
+    pub(crate) fn chooses(
+        &mut self,
+        f: impl FnOnce(&mut Candidates) -> Result<()>,
+    ) -> Result<bool> {
+        // When counting, or when an enclosing caller is already probing, only
+        // register the candidates and let any early exit propagate outwards.
+        if self.probing || self.counting() {
+            f(self)?;
+            return Ok(false);
+        }
+
+        let phase = self.phase;
+        let rounding_error = self.rounding_error;
+        self.probing = true;
+        let result = f(self);
+        self.probing = false;
+        match result {
+            Ok(()) => Ok(false),
+            Err(e) if e.is_early_exit() => {
+                self.phase = phase;
+                self.rounding_error = rounding_error;
+                Ok(true)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Whether only shrinking mutations should be registered in this mutation
     /// set or not.
     ///
@@ -392,6 +1102,37 @@ impl<'a> Candidates<'a> {
     pub fn shrink(&self) -> bool {
         self.context.shrink()
     }
+
+    /// Whether the value being mutated may grow by `additional` bytes without
+    /// exceeding the session's [maximum size][Session::max_size].
+    ///
+    /// When this method returns `false`, then you should not register any
+    /// mutation which grows the value by `additional` bytes.
+    #[inline]
+    pub fn can_grow(&self, additional: usize) -> bool {
+        self.context.can_grow(additional)
+    }
+
+    /// Run `f` one level deeper in the recursive mutator nesting, or return
+    /// `None` if we are already at the maximum depth.
+    #[inline]
+    pub(crate) fn nested<R>(&mut self, f: impl FnOnce(&mut Candidates) -> R) -> Option<R> {
+        if self.context.depth >= self.context.max_depth {
+            log::trace!("reached maximum depth of {}", self.context.max_depth);
+            return None;
+        }
+        self.context.depth += 1;
+        let result = f(self);
+        self.context.depth -= 1;
+        Some(result)
+    }
+
+    /// Whether we are only counting candidate mutations, and will therefore
+    /// not apply any of them during this `Mutate::mutate` call.
+    #[inline]
+    pub(crate) fn counting(&self) -> bool {
+        matches!(self.phase, Phase::Count(_))
+    }
 }
 
 /// A trait for mutating values.
@@ -778,6 +1519,59 @@ where
         mutators::Proj { mutator: self, f }
     }
 
+    /// Filter the mutations produced by this mutator, only keeping those whose
+    /// resulting value satisfies the given predicate.
+    ///
+    /// This is useful for maintaining invariants that the underlying mutator
+    /// does not know about, for example that a range's start is less than or
+    /// equal to its end.
+    ///
+    /// When a mutation produces a value that does not satisfy the predicate,
+    /// the original value is restored and another of this mutator's candidate
+    /// mutations is applied instead. This is retried up to a configurable
+    /// number of times (see [`Filter::retries`][mutators::Filter::retries]),
+    /// after which the original value is restored and an
+    /// [`Exhausted`][crate::error::ErrorKind::Exhausted] error is returned.
+    ///
+    /// Because it must restore the original value, this combinator requires
+    /// that `T: Clone`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn foo() -> mutatis::Result<()> {
+    /// use mutatis::{mutators as m, Mutate, Session};
+    ///
+    /// let mut session = Session::new();
+    ///
+    /// // Mutate pairs of `u32`s, but only ever produce pairs where the first
+    /// // element is less than or equal to the second.
+    /// let mut mutator = m::tuple2(m::u32(), m::u32()).filter(|(start, end)| start <= end);
+    ///
+    /// let mut value = (0, 100);
+    ///
+    /// for _ in 0..10 {
+    ///     session.mutate_with(&mut mutator, &mut value)?;
+    ///     assert!(value.0 <= value.1);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap()
+    /// ```
+    #[inline]
+    #[must_use = "mutator combinators do nothing until you call their `mutate` method"]
+    fn filter<P>(self, predicate: P) -> mutators::Filter<Self, P>
+    where
+        Self: Sized,
+        P: FnMut(&T) -> bool,
+    {
+        mutators::Filter {
+            mutator: self,
+            predicate,
+            retries: mutators::Filter::<Self, P>::DEFAULT_RETRIES,
+        }
+    }
+
     /// Borrows a mutator, rather than consuming it.
     ///
     /// This is useful to allow applying mutator adapters while still retaining
@@ -826,10 +1620,144 @@ where
     {
         self
     }
+
+    /// Box this mutator into a type-erased `Box<dyn DynMutate<T>>`.
+    ///
+    /// This is useful for storing mutators of different types in the same
+    /// collection, choosing mutators based on runtime configuration, or simply
+    /// avoiding enormous nested combinator types. See the [`DynMutate`] trait
+    /// for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn foo() -> mutatis::Result<()> {
+    /// use mutatis::{mutators as m, DynMutate, Mutate, Session};
+    ///
+    /// // Choose a mutator based on some runtime configuration.
+    /// let small_values_only = true;
+    /// let mut mutator: Box<dyn DynMutate<u32>> = if small_values_only {
+    ///     m::range(0..=100).boxed()
+    /// } else {
+    ///     m::u32().boxed()
+    /// };
+    ///
+    /// let mut session = Session::new();
+    /// let mut value = 42;
+    /// session.mutate_with(&mut mutator, &mut value)?;
+    /// assert!(value <= 100);
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap()
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use = "mutator combinators do nothing until you call their `mutate` method"]
+    fn boxed<'a>(self) -> alloc::boxed::Box<dyn DynMutate<T> + 'a>
+    where
+        Self: Sized + 'a,
+    {
+        alloc::boxed::Box::new(self)
+    }
+}
+
+/// An object-safe, type-erased mutator for `T` values.
+///
+/// This trait is automatically implemented for every [`Mutate<T>`]
+/// implementation, and `dyn DynMutate<T>` (as well as `Box<dyn DynMutate<T>>`
+/// and `&mut dyn DynMutate<T>`) implements `Mutate<T>` in turn. Use the
+/// [`Mutate::boxed`] method to create a `Box<dyn DynMutate<T>>` from any
+/// mutator.
+///
+/// Both `dyn DynMutate<T>` and `dyn Mutate<T>` implement
+/// [`Debug`][core::fmt::Debug], so boxed mutators can be stored in types that
+/// derive `Debug`.
+///
+/// # Example
+///
+/// Building a table of mutators from runtime configuration:
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{mutators as m, DynMutate, Mutate, Session};
+///
+/// #[derive(Debug)]
+/// struct Config {
+///     mutators: Vec<Box<dyn DynMutate<u64>>>,
+/// }
+///
+/// let mut config = Config { mutators: vec![] };
+/// for name in ["small", "any", "zero"] {
+///     config.mutators.push(match name {
+///         "small" => m::range(0..=10).boxed(),
+///         "any" => m::u64().boxed(),
+///         "zero" => m::just(0).boxed(),
+///         _ => unreachable!(),
+///     });
+/// }
+///
+/// let mut mutator = m::one_of(config.mutators);
+/// let mut session = Session::new();
+/// let mut value = 5;
+/// session.mutate_with(&mut mutator, &mut value)?;
+/// # Ok(())
+/// # }
+/// # #[cfg(feature = "alloc")]
+/// # foo().unwrap()
+/// ```
+pub trait DynMutate<T>: Mutate<T>
+where
+    T: ?Sized,
+{
+}
+
+impl<M, T> DynMutate<T> for M
+where
+    M: ?Sized + Mutate<T>,
+    T: ?Sized,
+{
+}
+
+impl<T> core::fmt::Debug for dyn DynMutate<T> + '_
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DynMutate").finish_non_exhaustive()
+    }
+}
+
+impl<T> core::fmt::Debug for dyn DynMutate<T> + Send + '_
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DynMutate").finish_non_exhaustive()
+    }
+}
+
+impl<T> core::fmt::Debug for dyn Mutate<T> + '_
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("dyn Mutate").finish_non_exhaustive()
+    }
+}
+
+impl<T> core::fmt::Debug for dyn Mutate<T> + Send + '_
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("dyn Mutate").finish_non_exhaustive()
+    }
 }
 
 fn _static_assert_object_safety(
     _: &dyn Mutate<u8>,
+    _: &dyn DynMutate<u8>,
     _: &dyn Generate<u8>,
     _: &dyn MutateInRange<u8>,
 ) {
@@ -837,7 +1765,19 @@ fn _static_assert_object_safety(
 
 impl<M, T> Mutate<T> for &mut M
 where
-    M: Mutate<T>,
+    M: ?Sized + Mutate<T>,
+    T: ?Sized,
+{
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        (**self).mutate(c, value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M, T> Mutate<T> for alloc::boxed::Box<M>
+where
+    M: ?Sized + Mutate<T>,
+    T: ?Sized,
 {
     fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
         (**self).mutate(c, value)
@@ -857,6 +1797,70 @@ pub trait Generate<T>: Mutate<T> {
     /// Implementations may use the `context`'s random number generator in the
     /// process of generating a `T`.
     fn generate(&mut self, context: &mut Context) -> Result<T>;
+
+    /// Estimate the size, in bytes, of a value that this generator generated.
+    ///
+    /// This is used to check newly generated sub-values against the session's
+    /// [maximum size][Session::max_size]. Returning `None`, which is the
+    /// default, means that the size is unknown, in which case the in-memory
+    /// size of `T` is used instead. Generators for values that own heap data
+    /// may override this, for example by using the value's [`SizeHint`]
+    /// implementation.
+    #[inline]
+    fn generated_size(&self, value: &T) -> Option<usize> {
+        let _ = value;
+        None
+    }
+
+    /// Estimate the size, in bytes, of the smallest value that this generator
+    /// may generate.
+    ///
+    /// This is used to avoid registering candidate mutations that generate
+    /// new sub-values when even the smallest such value would exceed the
+    /// session's [maximum size][Session::max_size]. Returning `None`, which is
+    /// the default, means that the in-memory size of `T` is used instead,
+    /// matching [`generated_size`][Generate::generated_size]'s default.
+    /// Generators that override `generated_size` should override this as
+    /// well, returning `Some(0)` if they know of no better lower bound.
+    #[inline]
+    fn min_generated_size(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<M, T> Generate<T> for &mut M
+where
+    M: ?Sized + Generate<T>,
+{
+    fn generate(&mut self, context: &mut Context) -> Result<T> {
+        (**self).generate(context)
+    }
+
+    fn generated_size(&self, value: &T) -> Option<usize> {
+        (**self).generated_size(value)
+    }
+
+    fn min_generated_size(&self) -> Option<usize> {
+        (**self).min_generated_size()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M, T> Generate<T> for alloc::boxed::Box<M>
+where
+    M: ?Sized + Generate<T>,
+{
+    fn generate(&mut self, context: &mut Context) -> Result<T> {
+        (**self).generate(context)
+    }
+
+    fn generated_size(&self, value: &T) -> Option<usize> {
+        (**self).generated_size(value)
+    }
+
+    fn min_generated_size(&self) -> Option<usize> {
+        (**self).min_generated_size()
+    }
 }
 
 /// A mutator that supports clamping mutated values to within a given range.
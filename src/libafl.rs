@@ -0,0 +1,167 @@
+//! Adapting `mutatis` mutators into [LibAFL](https://docs.rs/libafl) mutators.
+//!
+//! LibAFL has its own [`Mutator`] trait. The
+//! [`MutatisMutator`] type in this module wraps any `mutatis::Mutate<I>`
+//! implementation as a LibAFL mutator for inputs of type `I`, so that you can
+//! use your structure-aware `mutatis` mutators inside a LibAFL fuzzer.
+//!
+//! Each mutation reseeds the adapter's `mutatis::Session` from the LibAFL
+//! state's random number generator, so LibAFL remains in control of
+//! reproducibility, while state that persists across mutations, such as the
+//! session's [dictionary][Session::dict], [comparison
+//! operands][Session::cmp_log], and [adaptive scheduling][Session::adaptive]
+//! statistics, is kept.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # mod example {
+//! use libafl::inputs::Input;
+//! use mutatis::{libafl::MutatisMutator, Mutate};
+//! use serde::{Deserialize, Serialize};
+//!
+//! /// A structured input for our fuzzer.
+//! #[derive(Clone, Debug, Hash, Serialize, Deserialize, Mutate)]
+//! pub struct MyInput {
+//!     pub x: u32,
+//!     pub flag: bool,
+//! }
+//!
+//! impl Input for MyInput {}
+//!
+//! /// Mutate `MyInput`s with their default, derived mutator.
+//! pub fn mutator() -> MutatisMutator<<MyInput as mutatis::DefaultMutate>::DefaultMutate> {
+//!     MutatisMutator::new(mutatis::mutators::default::<MyInput>())
+//! }
+//! # }
+//! ```
+
+use super::*;
+use crate::log;
+use ::libafl::{
+    corpus::CorpusId,
+    mutators::{MutationResult, Mutator},
+    state::HasRand,
+};
+use ::libafl_bolts::{rands::Rand, Named};
+use std::borrow::Cow;
+use std::format;
+
+/// A LibAFL mutator that mutates inputs with a `mutatis` mutator.
+///
+/// When the `mutatis` mutator is exhausted, and therefore has no mutations to
+/// apply to the given input, this reports [`MutationResult::Skipped`] to
+/// LibAFL. Other `mutatis` errors are reported as LibAFL errors.
+///
+/// Whether LibAFL added the mutated input to its corpus is reported back to
+/// the session as [feedback][Session::feedback], so that [adaptive
+/// scheduling][Session::adaptive] works under LibAFL as well.
+///
+/// See [the module-level documentation][crate::libafl] for example usage.
+#[derive(Debug)]
+pub struct MutatisMutator<M> {
+    mutator: M,
+    session: Session,
+    name: Cow<'static, str>,
+}
+
+impl<M> MutatisMutator<M> {
+    /// Create a new LibAFL mutator that wraps the given `mutatis` mutator.
+    pub fn new(mutator: M) -> Self {
+        MutatisMutator {
+            mutator,
+            session: Session::new(),
+            name: Cow::Borrowed("MutatisMutator"),
+        }
+    }
+
+    /// Use the given `mutatis` session for mutations, for example one that is
+    /// configured with a [dictionary][Session::dict] or with
+    /// [adaptive scheduling][Session::adaptive].
+    ///
+    /// The session's seed is ignored: it is reseeded from the LibAFL state's
+    /// random number generator before every mutation.
+    ///
+    /// Defaults to `Session::new()`.
+    pub fn with_session(mut self, session: Session) -> Self {
+        self.session = session;
+        self
+    }
+
+    /// Get a shared reference to the `mutatis` session used for mutations.
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Get an exclusive reference to the `mutatis` session used for mutations,
+    /// for example to record [comparison operands][Session::cmp_log] observed
+    /// while executing the target.
+    pub fn session_mut(&mut self) -> &mut Session {
+        &mut self.session
+    }
+
+    /// Set the name that this mutator reports to LibAFL, for example in
+    /// statistics.
+    ///
+    /// Defaults to `"MutatisMutator"`.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Get a shared reference to the wrapped `mutatis` mutator.
+    pub fn inner(&self) -> &M {
+        &self.mutator
+    }
+
+    /// Get an exclusive reference to the wrapped `mutatis` mutator.
+    pub fn inner_mut(&mut self) -> &mut M {
+        &mut self.mutator
+    }
+
+    /// Unwrap this LibAFL mutator, returning the wrapped `mutatis` mutator.
+    pub fn into_inner(self) -> M {
+        self.mutator
+    }
+}
+
+impl<M> Named for MutatisMutator<M> {
+    fn name(&self) -> &Cow<'static, str> {
+        &self.name
+    }
+}
+
+impl<M, I, S> Mutator<I, S> for MutatisMutator<M>
+where
+    M: Mutate<I>,
+    S: HasRand,
+{
+    fn mutate(
+        &mut self,
+        state: &mut S,
+        input: &mut I,
+    ) -> core::result::Result<MutationResult, ::libafl::Error> {
+        self.session.context.rng = Rng::new(state.rand_mut().next());
+        match self.session.mutate_with(&mut self.mutator, input) {
+            Ok(()) => Ok(MutationResult::Mutated),
+            Err(e) if e.is_exhausted() => {
+                log::trace!("mutator exhausted, skipping mutation");
+                Ok(MutationResult::Skipped)
+            }
+            Err(e) => Err(::libafl::Error::unknown(format!(
+                "mutatis mutator failed: {e}"
+            ))),
+        }
+    }
+
+    fn post_exec(
+        &mut self,
+        _state: &mut S,
+        new_corpus_id: Option<CorpusId>,
+    ) -> core::result::Result<(), ::libafl::Error> {
+        // LibAFL only adds inputs to the corpus when they were interesting.
+        self.session.feedback(new_corpus_id.is_some());
+        Ok(())
+    }
+}
@@ -0,0 +1,504 @@
+//! Ready-made glue for using `mutatis` mutators with `libfuzzer`.
+//!
+//! The [`libfuzzer_sys::fuzz_mutator!`][fuzz-mutator] and
+//! [`libfuzzer_sys::fuzz_crossover!`][fuzz-crossover] macros let you customize
+//! how `libfuzzer` mutates and combines test cases. This module provides
+//! helpers that implement the bodies of those macros for you: they decode the
+//! fuzzer's raw bytes into a structured value with a [`Codec`], mutate (or
+//! combine) that structured value, and then encode the result back into the
+//! fuzzer's buffer, falling back to `libfuzzer`'s default mutation strategies
+//! whenever that is not possible.
+//!
+//! [fuzz-mutator]: https://docs.rs/libfuzzer-sys/latest/libfuzzer_sys/macro.fuzz_mutator.html
+//! [fuzz-crossover]: https://docs.rs/libfuzzer-sys/latest/libfuzzer_sys/macro.fuzz_crossover.html
+//!
+//! # Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "derive")]
+//! # mod example {
+//! use libfuzzer_sys::{fuzz_crossover, fuzz_mutator, fuzz_target};
+//! use mutatis::{codec::Codec, Mutate};
+//!
+//! /// A red-green-blue color.
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Mutate)]
+//! pub struct Rgb([u8; 3]);
+//!
+//! /// Encode `Rgb` colors as their three raw bytes.
+//! pub struct RgbCodec;
+//!
+//! impl Codec<Rgb> for RgbCodec {
+//!     fn decode(bytes: &[u8]) -> Option<Rgb> {
+//!         bytes.first_chunk::<3>().map(|b| Rgb(*b))
+//!     }
+//!
+//!     fn encode(rgb: &Rgb, bytes: &mut Vec<u8>) {
+//!         bytes.extend_from_slice(&rgb.0);
+//!     }
+//! }
+//!
+//! fuzz_target!(|data: &[u8]| {
+//!     if let Some(rgb) = RgbCodec::decode(data) {
+//!         // Test your code with the structured `rgb` value...
+//!     }
+//! });
+//!
+//! // Mutate the fuzzer's test cases with `Rgb`'s default, derived mutator.
+//! fuzz_mutator!(|data: &mut [u8], size: usize, max_size: usize, seed: u32| {
+//!     mutatis::libfuzzer::mutate::<Rgb, RgbCodec>(data, size, max_size, seed)
+//! });
+//!
+//! // Combine two test cases by taking each color channel from either parent.
+//! fuzz_crossover!(|data1: &[u8], data2: &[u8], out: &mut [u8], seed: u32| {
+//!     mutatis::libfuzzer::crossover::<Rgb, RgbCodec>(
+//!         data1,
+//!         data2,
+//!         out,
+//!         seed,
+//!         |a, b, rng| {
+//!             let mut child = a;
+//!             for (c, b) in child.0.iter_mut().zip(b.0) {
+//!                 if rng.gen_bool() {
+//!                     *c = b;
+//!                 }
+//!             }
+//!             child
+//!         },
+//!     )
+//! });
+//! # }
+//! ```
+
+use super::*;
+use crate::codec::Codec;
+use crate::log;
+use core::mem;
+use std::vec::Vec;
+
+/// Mutate a `libfuzzer` test case with `T`'s default mutator.
+///
+/// This is intended to be used as the body of a
+/// [`libfuzzer_sys::fuzz_mutator!`][fuzz-mutator] invocation, and takes the
+/// same arguments and returns the same result as that macro's closure.
+///
+/// The test case's raw bytes, `data[..size]`, are decoded into a `T` value with
+/// the codec `C`. Then that value is mutated with a `Session` seeded with
+/// `seed`, whose [maximum size][Session::max_size] is `max_size` and which
+/// treats `size` as the value's current size. Finally, the mutated value is
+/// encoded back into `data` and its new size is returned.
+///
+/// If the raw bytes cannot be decoded, the mutator fails, or the encoded
+/// mutated value would be larger than `max_size`, then this falls back to
+/// `libfuzzer`'s default mutation strategies via
+/// [`libfuzzer_sys::fuzzer_mutate`]. Encoded values are never truncated, since
+/// a truncated encoding is rarely a valid encoding.
+///
+/// See [the module-level documentation][crate::libfuzzer] for example usage.
+///
+/// [fuzz-mutator]: https://docs.rs/libfuzzer-sys/latest/libfuzzer_sys/macro.fuzz_mutator.html
+pub fn mutate<T, C>(data: &mut [u8], size: usize, max_size: usize, seed: u32) -> usize
+where
+    T: DefaultMutate,
+    C: Codec<T>,
+{
+    let mut mutator = T::DefaultMutate::default();
+    mutate_with::<T, C>(&mut mutator, data, size, max_size, seed)
+}
+
+/// Mutate a `libfuzzer` test case with the given mutator.
+///
+/// This is like [`mutate`] but uses the given `mutator` rather than `T`'s
+/// default mutator.
+pub fn mutate_with<T, C>(
+    mutator: &mut impl Mutate<T>,
+    data: &mut [u8],
+    size: usize,
+    max_size: usize,
+    seed: u32,
+) -> usize
+where
+    C: Codec<T>,
+{
+    mutate_with_session::<T, C>(&mut Session::new(), mutator, data, size, max_size, seed)
+}
+
+/// Mutate a `libfuzzer` test case with the given mutator and session.
+///
+/// This is like [`mutate_with`] but uses the given `session` rather than a
+/// fresh session for every test case, so that state which persists across
+/// mutations is taken into account: the session's
+/// [comparison operands][Session::cmp_log], [dictionary][Session::dict], and
+/// [adaptive scheduling][Session::adaptive] statistics. The session is
+/// temporarily reseeded with `seed` and its [maximum size][Session::max_size]
+/// is temporarily set to `max_size` while mutating the test case; both are
+/// restored afterwards.
+///
+/// # Example
+///
+/// ```no_run
+/// use libfuzzer_sys::{fuzz_mutator, fuzz_target};
+/// use mutatis::{mutators as m, Session};
+/// use std::sync::{Mutex, OnceLock};
+///
+/// fn session() -> &'static Mutex<Session> {
+///     static SESSION: OnceLock<Mutex<Session>> = OnceLock::new();
+///     SESSION.get_or_init(|| Mutex::new(Session::new()))
+/// }
+///
+/// # struct LittleEndian;
+/// # impl mutatis::codec::Codec<u32> for LittleEndian {
+/// #     fn decode(bytes: &[u8]) -> Option<u32> {
+/// #         bytes.first_chunk::<4>().map(|b| u32::from_le_bytes(*b))
+/// #     }
+/// #     fn encode(value: &u32, bytes: &mut Vec<u8>) {
+/// #         bytes.extend_from_slice(&value.to_le_bytes());
+/// #     }
+/// # }
+/// fuzz_target!(|data: &[u8]| {
+///     if let Some(x) = <LittleEndian as mutatis::codec::Codec<u32>>::decode(data) {
+///         // Record the operands of the comparisons that the code under test
+///         // performs, so that subsequent mutations can solve them.
+///         session().lock().unwrap().cmp_log().observe_u32(x, 0xcafebabe);
+///         if x == 0xcafebabe {
+///             // ...
+///         }
+///     }
+/// });
+///
+/// fuzz_mutator!(|data: &mut [u8], size: usize, max_size: usize, seed: u32| {
+///     mutatis::libfuzzer::mutate_with_session::<u32, LittleEndian>(
+///         &mut session().lock().unwrap(),
+///         &mut m::u32(),
+///         data,
+///         size,
+///         max_size,
+///         seed,
+///     )
+/// });
+/// ```
+pub fn mutate_with_session<T, C>(
+    session: &mut Session,
+    mutator: &mut impl Mutate<T>,
+    data: &mut [u8],
+    size: usize,
+    max_size: usize,
+    seed: u32,
+) -> usize
+where
+    C: Codec<T>,
+{
+    mutate_impl::<T, C>(
+        session,
+        mutator,
+        data,
+        size,
+        max_size,
+        seed,
+        libfuzzer_sys::fuzzer_mutate,
+    )
+}
+
+fn mutate_impl<T, C>(
+    session: &mut Session,
+    mutator: &mut impl Mutate<T>,
+    data: &mut [u8],
+    size: usize,
+    max_size: usize,
+    seed: u32,
+    fallback: impl FnOnce(&mut [u8], usize, usize) -> usize,
+) -> usize
+where
+    C: Codec<T>,
+{
+    let mut value = match C::decode(&data[..size]) {
+        Some(v) => v,
+        None => {
+            log::trace!("failed to decode test case, using fallback mutation");
+            return fallback(data, size, max_size);
+        }
+    };
+
+    // Pass libFuzzer's seed and size budget through to the mutators, using the
+    // test case's encoded size as the value's current size, and then restore
+    // the session's own configuration.
+    let rng = mem::replace(&mut session.context.rng, Rng::new(seed.into()));
+    let max_size_config = session.context.max_size.replace(max_size);
+    session.context.begin_mutation();
+    let result = session
+        .context
+        .with_size(size, |ctx| ctx.mutate_with(mutator, &mut value));
+    session.context.rng = rng;
+    session.context.max_size = max_size_config;
+    if let Err(e) = result {
+        log::trace!("failed to mutate test case, using fallback mutation: {e}");
+        return fallback(data, size, max_size);
+    }
+
+    let mut bytes = Vec::with_capacity(size);
+    C::encode(&value, &mut bytes);
+    if bytes.len() > max_size || bytes.len() > data.len() {
+        log::trace!(
+            "mutated test case is too large ({} > {max_size}), using fallback mutation",
+            bytes.len()
+        );
+        return fallback(data, size, max_size);
+    }
+
+    data[..bytes.len()].copy_from_slice(&bytes);
+    bytes.len()
+}
+
+/// Combine two `libfuzzer` test cases into a new test case.
+///
+/// This is intended to be used as the body of a
+/// [`libfuzzer_sys::fuzz_crossover!`][fuzz-crossover] invocation, and takes
+/// the same arguments and returns the same result as that macro's closure, plus
+/// a `cross` function that combines two structured `T` values into a new `T`
+/// value.
+///
+/// The raw bytes `data1` and `data2` are decoded into `T` values with the codec
+/// `C`, combined with `cross` (which is given an `Rng` seeded with `seed`), and
+/// the result is encoded into `out`. The size of the new test case is returned.
+///
+/// If either test case cannot be decoded, or the encoded result would not fit
+/// in `out`, then this falls back to a simple byte-level crossover that
+/// splices a prefix of `data1` together with a suffix of `data2`.
+///
+/// See [the module-level documentation][crate::libfuzzer] for example usage.
+///
+/// [fuzz-crossover]: https://docs.rs/libfuzzer-sys/latest/libfuzzer_sys/macro.fuzz_crossover.html
+pub fn crossover<T, C>(
+    data1: &[u8],
+    data2: &[u8],
+    out: &mut [u8],
+    seed: u32,
+    cross: impl FnOnce(T, T, &mut Rng) -> T,
+) -> usize
+where
+    C: Codec<T>,
+{
+    let mut rng = Rng::new(seed.into());
+
+    let (a, b) = match (C::decode(data1), C::decode(data2)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => {
+            log::trace!("failed to decode test cases, using fallback crossover");
+            return splice(&mut rng, data1, data2, out);
+        }
+    };
+
+    let child = cross(a, b, &mut rng);
+
+    let mut bytes = Vec::with_capacity(out.len());
+    C::encode(&child, &mut bytes);
+    if bytes.len() > out.len() {
+        log::trace!(
+            "crossed-over test case is too large ({} > {}), using fallback crossover",
+            bytes.len(),
+            out.len()
+        );
+        return splice(&mut rng, data1, data2, out);
+    }
+
+    out[..bytes.len()].copy_from_slice(&bytes);
+    bytes.len()
+}
+
+/// Byte-level crossover: write a random prefix of `data1` followed by a random
+/// suffix of `data2` into `out`, truncating to `out`'s length.
+fn splice(rng: &mut Rng, data1: &[u8], data2: &[u8], out: &mut [u8]) -> usize {
+    let prefix = &data1[..rng.gen_index(data1.len() + 1).unwrap()];
+    let suffix = &data2[rng.gen_index(data2.len() + 1).unwrap()..];
+
+    let mut size = 0;
+    for &byte in prefix.iter().chain(suffix).take(out.len()) {
+        out[size] = byte;
+        size += 1;
+    }
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutators as m;
+
+    struct LittleEndian;
+
+    impl Codec<u32> for LittleEndian {
+        fn decode(bytes: &[u8]) -> Option<u32> {
+            let bytes = bytes.first_chunk::<4>()?;
+            Some(u32::from_le_bytes(*bytes))
+        }
+
+        fn encode(value: &u32, bytes: &mut Vec<u8>) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    fn no_fallback(_: &mut [u8], _: usize, _: usize) -> usize {
+        unreachable!("should not fall back to libfuzzer's mutator")
+    }
+
+    fn fallback(_: &mut [u8], _: usize, _: usize) -> usize {
+        usize::MAX
+    }
+
+    #[test]
+    fn mutate_encodes_mutated_value() {
+        let mut data = [0; 8];
+        data[..4].copy_from_slice(&42_u32.to_le_bytes());
+
+        let new_size = mutate_impl::<u32, LittleEndian>(
+            &mut Session::new(),
+            &mut m::u32(),
+            &mut data,
+            4,
+            8,
+            1,
+            no_fallback,
+        );
+        assert_eq!(new_size, 4);
+
+        let new_value = u32::from_le_bytes(*data.first_chunk::<4>().unwrap());
+        assert_ne!(new_value, 42);
+    }
+
+    #[test]
+    fn mutate_is_deterministic() {
+        let run = || {
+            let mut data = 42_u32.to_le_bytes();
+            mutate_impl::<u32, LittleEndian>(
+                &mut Session::new(),
+                &mut m::u32(),
+                &mut data,
+                4,
+                4,
+                5,
+                no_fallback,
+            );
+            data
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn mutate_falls_back_on_decode_failure() {
+        let mut data = [0; 8];
+        let new_size = mutate_impl::<u32, LittleEndian>(
+            &mut Session::new(),
+            &mut m::u32(),
+            &mut data,
+            3,
+            8,
+            1,
+            fallback,
+        );
+        assert_eq!(new_size, usize::MAX);
+    }
+
+    #[test]
+    fn mutate_falls_back_on_mutator_error() {
+        struct Failing;
+        impl Mutate<u32> for Failing {
+            fn mutate(&mut self, _c: &mut Candidates, _value: &mut u32) -> Result<()> {
+                Err(Error::exhausted())
+            }
+        }
+
+        let mut data = 42_u32.to_le_bytes();
+        let new_size = mutate_impl::<u32, LittleEndian>(
+            &mut Session::new(),
+            &mut Failing,
+            &mut data,
+            4,
+            4,
+            1,
+            fallback,
+        );
+        assert_eq!(new_size, usize::MAX);
+    }
+
+    #[test]
+    fn mutate_falls_back_instead_of_truncating() {
+        let mut data = 42_u32.to_le_bytes();
+        let new_size = mutate_impl::<u32, LittleEndian>(
+            &mut Session::new(),
+            &mut m::u32(),
+            &mut data,
+            4,
+            3,
+            1,
+            fallback,
+        );
+        assert_eq!(new_size, usize::MAX);
+        assert_eq!(data, 42_u32.to_le_bytes());
+    }
+
+    #[test]
+    fn mutate_restores_session_config() {
+        let mut session = Session::new().seed(7).max_size(100);
+        let mut data = 42_u32.to_le_bytes();
+        mutate_impl::<u32, LittleEndian>(
+            &mut session,
+            &mut m::u32(),
+            &mut data,
+            4,
+            4,
+            1,
+            no_fallback,
+        );
+        assert_eq!(session.context.max_size, Some(100));
+        assert_eq!(
+            session.context.rng().gen_u64(),
+            Session::new().seed(7).context.rng().gen_u64()
+        );
+    }
+
+    #[test]
+    fn mutate_uses_session_cmp_log() {
+        let mut session = Session::new();
+        session.cmp_log().observe_u32(42, 0xcafebabe);
+        let found = (0..1000).any(|seed| {
+            let mut data = 42_u32.to_le_bytes();
+            mutate_impl::<u32, LittleEndian>(
+                &mut session,
+                &mut m::u32(),
+                &mut data,
+                4,
+                4,
+                seed,
+                no_fallback,
+            );
+            data == 0xcafebabe_u32.to_le_bytes()
+        });
+        assert!(found);
+    }
+
+    #[test]
+    fn crossover_combines_values() {
+        let mut out = [0; 8];
+        let new_size = crossover::<u32, LittleEndian>(
+            &1_u32.to_le_bytes(),
+            &2_u32.to_le_bytes(),
+            &mut out,
+            1,
+            |a, b, _rng| a + b,
+        );
+        assert_eq!(new_size, 4);
+        assert_eq!(out[..4], 3_u32.to_le_bytes());
+    }
+
+    #[test]
+    fn crossover_splices_on_decode_failure() {
+        let mut out = [0; 8];
+        let new_size =
+            crossover::<u32, LittleEndian>(b"aaa", b"bbb", &mut out, 1, |_, _, _| unreachable!());
+        assert!(new_size <= 6);
+
+        // A prefix of `data1` followed by a suffix of `data2`.
+        let child = &out[..new_size];
+        let num_a = child.iter().take_while(|b| **b == b'a').count();
+        assert!(child[num_a..].iter().all(|b| *b == b'b'));
+    }
+}
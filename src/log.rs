@@ -1,39 +1,63 @@
 #![allow(unused_macros, unused_imports)]
 
+// When the `log` feature is disabled, these macros still type check (but never
+// evaluate) their format arguments, so that variables which are only used in
+// log messages do not trigger unused-variable warnings.
+
 #[cfg(feature = "log")]
 pub(crate) use ::log::{debug, error, info, trace, warn};
 
 #[cfg(not(feature = "log"))]
 macro_rules! debug {
-    ($($tt:tt)*) => {};
+    ($($tt:tt)*) => {
+        if false {
+            let _ = format_args!($($tt)*);
+        }
+    };
 }
 #[cfg(not(feature = "log"))]
 pub(crate) use debug;
 
 #[cfg(not(feature = "log"))]
 macro_rules! error {
-    ($($tt:tt)*) => {};
+    ($($tt:tt)*) => {
+        if false {
+            let _ = format_args!($($tt)*);
+        }
+    };
 }
 #[cfg(not(feature = "log"))]
 pub(crate) use error;
 
 #[cfg(not(feature = "log"))]
 macro_rules! info {
-    ($($tt:tt)*) => {};
+    ($($tt:tt)*) => {
+        if false {
+            let _ = format_args!($($tt)*);
+        }
+    };
 }
 #[cfg(not(feature = "log"))]
 pub(crate) use info;
 
 #[cfg(not(feature = "log"))]
 macro_rules! trace {
-    ($($tt:tt)*) => {};
+    ($($tt:tt)*) => {
+        if false {
+            let _ = format_args!($($tt)*);
+        }
+    };
 }
 #[cfg(not(feature = "log"))]
 pub(crate) use trace;
 
 #[cfg(not(feature = "log"))]
 macro_rules! warn_impl {
-    ($($tt:tt)*) => {};
+    ($($tt:tt)*) => {
+        if false {
+            let _ = format_args!($($tt)*);
+        }
+    };
 }
 #[cfg(not(feature = "log"))]
 pub(crate) use warn_impl as warn;
@@ -10,15 +10,20 @@ use super::*;
 use ::core::ops;
 use rand::Rng;
 
+#[cfg(feature = "alloc")]
+mod alloc_impls;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 mod combinators;
 mod core_impls;
 
+#[cfg(feature = "arbitrary")]
+pub use self::arbitrary::*;
+#[cfg(feature = "alloc")]
+pub use alloc_impls::*;
 pub use combinators::*;
 pub use core_impls::*;
 
-// TODO: mod alloc;
-// TODO: pub use alloc::*;
-
 // TODO: mod std;
 // TODO: pub use std::*;
 
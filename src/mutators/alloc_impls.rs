@@ -0,0 +1,76 @@
+use super::*;
+use crate::Result;
+
+/// A mutator for `Box<T>` values.
+///
+/// See the [`boxed()`] function to create a new `Box` mutator and for example
+/// usage.
+#[derive(Clone, Debug, Default)]
+pub struct Box<M> {
+    mutator: M,
+}
+
+/// Create a new mutator for `Box<T>` values, given a mutator for `T` values.
+///
+/// Not to be confused with the [`Mutate::boxed`] method, which boxes a mutator
+/// itself, rather than creating a mutator for boxed values.
+///
+/// The default mutator for `Box<T>` values is `m::Box<m::LazyDefault<T>>`,
+/// which lazily constructs `T`'s default mutator. This allows deriving
+/// `Mutate` for recursive types, and respects the
+/// [maximum depth][crate::Session::max_depth] of the mutation session. See
+/// [`lazy_default`] for details.
+///
+/// # Example
+///
+/// ```
+/// use mutatis::{mutators as m, Mutate, Session};
+///
+/// let mut mutator = m::boxed(m::u32());
+/// let mut session = Session::new();
+///
+/// let mut value = Box::new(36);
+/// session.mutate_with(&mut mutator, &mut value).unwrap();
+///
+/// println!("mutated box is {value:?}");
+/// ```
+pub fn boxed<M>(mutator: M) -> Box<M> {
+    Box { mutator }
+}
+
+impl<M, T> Mutate<alloc::boxed::Box<T>> for Box<M>
+where
+    M: Mutate<T>,
+{
+    #[inline]
+    fn mutate(&mut self, c: &mut Candidates, value: &mut alloc::boxed::Box<T>) -> Result<()> {
+        self.mutator.mutate(c, &mut **value)
+    }
+}
+
+impl<M, T> Generate<alloc::boxed::Box<T>> for Box<M>
+where
+    M: Generate<T>,
+{
+    #[inline]
+    fn generate(&mut self, ctx: &mut Context) -> Result<alloc::boxed::Box<T>> {
+        Ok(alloc::boxed::Box::new(self.mutator.generate(ctx)?))
+    }
+
+    #[inline]
+    fn generated_size(&self, value: &alloc::boxed::Box<T>) -> core::option::Option<usize> {
+        self.mutator.generated_size(value)
+    }
+
+    #[inline]
+    fn min_generated_size(&self) -> core::option::Option<usize> {
+        self.mutator.min_generated_size()
+    }
+}
+
+impl<T> DefaultMutate for alloc::boxed::Box<T>
+where
+    T: DefaultMutate,
+{
+    type DefaultMutate = Box<LazyDefault<T>>;
+}
@@ -0,0 +1,148 @@
+use super::*;
+use ::arbitrary::{Arbitrary, Unstructured};
+use alloc::{format, vec};
+use core::marker::PhantomData;
+
+/// The maximum number of random bytes, beyond the type's minimum size, to feed
+/// into `Arbitrary` when the type's size hint does not give a smaller upper
+/// bound.
+const MAX_ARBITRARY_BYTES: usize = 4096;
+
+/// A mutator for `T` values that implement
+/// [`arbitrary::Arbitrary`](https://docs.rs/arbitrary/latest/arbitrary/trait.Arbitrary.html).
+///
+/// See the [`from_arbitrary`] function to create a new `FromArbitrary` mutator
+/// and for example usage.
+pub struct FromArbitrary<T> {
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for FromArbitrary<T> {
+    fn clone(&self) -> Self {
+        from_arbitrary()
+    }
+}
+
+impl<T> core::fmt::Debug for FromArbitrary<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FromArbitrary").finish()
+    }
+}
+
+impl<T> Default for FromArbitrary<T> {
+    fn default() -> Self {
+        from_arbitrary()
+    }
+}
+
+/// Create a new mutator for `T` values that implement
+/// [`arbitrary::Arbitrary`](https://docs.rs/arbitrary/latest/arbitrary/trait.Arbitrary.html).
+///
+/// This lets types that only implement `Arbitrary`, and not `mutatis`'s
+/// traits, be used as leaves inside other `mutatis` mutators, such as
+/// [`option`][crate::mutators::option()].
+///
+/// New values are generated by feeding random bytes into an
+/// [`arbitrary::Unstructured`](https://docs.rs/arbitrary/latest/arbitrary/struct.Unstructured.html).
+/// Because `Arbitrary` has no notion of incremental mutation, this mutator
+/// mutates a value by replacing it with an entirely new, arbitrary value.
+/// Neither can it tell whether a new value is smaller than the original, so
+/// it does not register any mutations when shrinking.
+///
+/// When the `Arbitrary` implementation fails with
+/// `arbitrary::Error::NotEnoughData` or `arbitrary::Error::IncorrectFormat`,
+/// the mutation is treated as exhausted, so that callers skip it rather than
+/// fail.
+///
+/// Requires the `arbitrary` cargo feature.
+///
+/// # Example
+///
+/// ```
+/// # fn foo() -> mutatis::Result<()> {
+/// use arbitrary::Arbitrary;
+/// use mutatis::{mutators as m, Mutate, Session};
+///
+/// /// A type that implements `Arbitrary` but not `mutatis::Mutate`.
+/// #[derive(Arbitrary, Debug)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// // Use the `Arbitrary` implementation for the `Point` inside an `Option`.
+/// let mut mutator = m::option(m::from_arbitrary::<Point>());
+/// let mut session = Session::new();
+///
+/// let mut value = None;
+/// for _ in 0..5 {
+///     session.mutate_with(&mut mutator, &mut value)?;
+///     println!("mutated value is {value:?}");
+/// }
+///
+/// // Example output:
+/// //
+/// //     mutated value is Some(Point { x: -1170106454, y: 1306367417 })
+/// //     mutated value is None
+/// //     mutated value is Some(Point { x: 1962376640, y: -498437279 })
+/// //     mutated value is Some(Point { x: 45181744, y: -1541128416 })
+/// //     mutated value is None
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+pub fn from_arbitrary<T>() -> FromArbitrary<T> {
+    FromArbitrary {
+        _phantom: PhantomData,
+    }
+}
+
+impl<T> Mutate<T> for FromArbitrary<T>
+where
+    T: for<'a> Arbitrary<'a>,
+{
+    #[inline]
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        if c.shrink() {
+            return Ok(());
+        }
+        c.named_mutation("arbitrary", |ctx| {
+            *value = self.generate(ctx)?;
+            Ok(())
+        })
+    }
+}
+
+impl<T> Generate<T> for FromArbitrary<T>
+where
+    T: for<'a> Arbitrary<'a>,
+{
+    fn generate(&mut self, ctx: &mut Context) -> Result<T> {
+        // Choose a random number of bytes to feed into `Arbitrary`, respecting
+        // the type's size hint and the remaining size budget. Huge upper
+        // bounds, such as `(0, Some(usize::MAX))`, are treated like unbounded
+        // ones.
+        let (min, max) = T::size_hint(0);
+        let max = max
+            .unwrap_or(usize::MAX)
+            .min(min.saturating_add(MAX_ARBITRARY_BYTES));
+        let max = match ctx.remaining_size() {
+            Some(remaining) => max.min(remaining).max(min),
+            None => max,
+        };
+        let len = min + ctx.rng().gen_index((max - min).saturating_add(1)).unwrap();
+
+        let mut bytes = vec![0; len];
+        ctx.rng().gen_bytes(&mut bytes);
+
+        // Running out of data or rejecting the random bytes are expected
+        // outcomes for some `Arbitrary` implementations, so treat them like any
+        // other mutation that could not be applied, rather than as failures.
+        T::arbitrary_take_rest(Unstructured::new(&bytes)).map_err(|e| match e {
+            ::arbitrary::Error::NotEnoughData | ::arbitrary::Error::IncorrectFormat => {
+                Error::exhausted()
+            }
+            e => Error::other(format!("failed to generate arbitrary value: {e}")),
+        })
+    }
+}
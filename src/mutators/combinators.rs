@@ -1,5 +1,5 @@
 use super::*;
-use crate::Result;
+use crate::{log, Result};
 
 /// A mutator combinator for applying one of two different mutators.
 ///
@@ -49,6 +49,1158 @@ where
     }
 }
 
+/// A mutator combinator for only keeping the mutations produced by another
+/// mutator that satisfy a predicate.
+///
+/// See the [`filter`][Mutate::filter] method on the [`Mutate`] trait for
+/// details and example usage.
+#[derive(Clone, Debug)]
+pub struct Filter<M, P> {
+    pub(crate) mutator: M,
+    pub(crate) predicate: P,
+    pub(crate) retries: usize,
+}
+
+impl<M, P> Filter<M, P> {
+    pub(crate) const DEFAULT_RETRIES: usize = 100;
+
+    /// Configure the number of times to retry with another mutation when a
+    /// mutation produces a value that does not satisfy the predicate, before
+    /// giving up and returning an [`Exhausted`][crate::error::ErrorKind::Exhausted]
+    /// error.
+    ///
+    /// Defaults to `100`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mutatis::{mutators as m, Mutate, Session};
+    ///
+    /// // A predicate that is only satisfied by a single `u32` value is
+    /// // unlikely to be satisfied within a single retry.
+    /// let mut mutator = m::u32().filter(|x| *x == 42).retries(1);
+    ///
+    /// let mut value = 0;
+    /// let mut session = Session::new();
+    /// match session.mutate_with(&mut mutator, &mut value) {
+    ///     Ok(()) => assert_eq!(value, 42),
+    ///     Err(e) => {
+    ///         assert!(e.is_exhausted());
+    ///         // The original value is restored.
+    ///         assert_eq!(value, 0);
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    #[must_use = "mutator combinators do nothing until you call their `mutate` method"]
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+}
+
+impl<M, P, T> Mutate<T> for Filter<M, P>
+where
+    M: Mutate<T>,
+    P: FnMut(&T) -> bool,
+    T: Clone,
+{
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        // Only snapshot the value when one of the underlying mutator's
+        // candidates is going to be applied to it. Otherwise there is nothing
+        // to check or restore.
+        if !c.chooses(|c| self.mutator.mutate(c, value))? {
+            return Ok(());
+        }
+
+        let snapshot = value.clone();
+        match self.mutator.mutate(c, value) {
+            Err(e) if e.is_early_exit() => {
+                if (self.predicate)(value) {
+                    return Err(e);
+                }
+
+                // The chosen mutation broke the predicate. Restore the
+                // original value and try other randomly-chosen mutations of
+                // the underlying mutator instead.
+                for _ in 0..self.retries {
+                    *value = snapshot.clone();
+                    match c.context.mutate_with(&mut self.mutator, value) {
+                        Ok(()) if (self.predicate)(value) => return Err(Error::early_exit()),
+                        Ok(()) => {}
+                        Err(e) if e.is_exhausted() => break,
+                        Err(e) => {
+                            *value = snapshot;
+                            return Err(e);
+                        }
+                    }
+                }
+
+                log::trace!("filter predicate never satisfied, giving up");
+                *value = snapshot;
+                Err(Error::exhausted())
+            }
+            Ok(()) => Ok(()),
+            Err(e) => {
+                *value = snapshot;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<M, P, T> Generate<T> for Filter<M, P>
+where
+    M: Generate<T>,
+    P: FnMut(&T) -> bool,
+    T: Clone,
+{
+    fn generate(&mut self, ctx: &mut Context) -> Result<T> {
+        for _ in 0..=self.retries {
+            let value = self.mutator.generate(ctx)?;
+            if (self.predicate)(&value) {
+                return Ok(value);
+            }
+        }
+        log::trace!("filter predicate never satisfied, giving up");
+        Err(Error::exhausted())
+    }
+
+    fn generated_size(&self, value: &T) -> core::option::Option<usize> {
+        self.mutator.generated_size(value)
+    }
+
+    fn min_generated_size(&self) -> core::option::Option<usize> {
+        self.mutator.min_generated_size()
+    }
+}
+
+/// A collection of mutators for `T` values, for use with the [`one_of`] and
+/// [`frequency`] combinators.
+///
+/// This trait is implemented for tuples of up to 16 mutators, arrays of
+/// mutators, and (when the `alloc` feature is enabled) vectors of mutators. To
+/// choose among mutators of different types stored in the same array or
+/// vector, box them with [`Mutate::boxed`].
+pub trait Mutators<T> {
+    /// The number of mutators in this collection.
+    fn len(&self) -> usize;
+
+    /// Is this collection empty?
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the `index`th mutator in this collection.
+    ///
+    /// Panics if `index` is out of bounds.
+    fn mutator(&mut self, index: usize) -> &mut dyn Mutate<T>;
+}
+
+/// A collection of generators for `T` values, for use with the [`one_of`] and
+/// [`frequency`] combinators.
+///
+/// This is the [`Generate`] equivalent of [`Mutators`].
+pub trait Generators<T>: Mutators<T> {
+    /// Get the `index`th generator in this collection.
+    ///
+    /// Panics if `index` is out of bounds.
+    fn generator(&mut self, index: usize) -> &mut dyn Generate<T>;
+}
+
+impl<M, T, const N: usize> Mutators<T> for [M; N]
+where
+    M: Mutate<T>,
+{
+    fn len(&self) -> usize {
+        N
+    }
+
+    fn mutator(&mut self, index: usize) -> &mut dyn Mutate<T> {
+        &mut self[index]
+    }
+}
+
+impl<M, T, const N: usize> Generators<T> for [M; N]
+where
+    M: Generate<T>,
+{
+    fn generator(&mut self, index: usize) -> &mut dyn Generate<T> {
+        &mut self[index]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M, T> Mutators<T> for alloc::vec::Vec<M>
+where
+    M: Mutate<T>,
+{
+    fn len(&self) -> usize {
+        alloc::vec::Vec::len(self)
+    }
+
+    fn mutator(&mut self, index: usize) -> &mut dyn Mutate<T> {
+        &mut self[index]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M, T> Generators<T> for alloc::vec::Vec<M>
+where
+    M: Generate<T>,
+{
+    fn generator(&mut self, index: usize) -> &mut dyn Generate<T> {
+        &mut self[index]
+    }
+}
+
+macro_rules! tuple_mutators {
+    ( $( ( $( $idx:tt : $m:ident , )* ) ; )* ) => {
+        $(
+            impl<T, $( $m , )*> Mutators<T> for ( $( $m , )* )
+            where
+                $(
+                    $m: Mutate<T>,
+                )*
+            {
+                fn len(&self) -> usize {
+                    tuple_mutators!(@count $( $m )*)
+                }
+
+                fn mutator(&mut self, index: usize) -> &mut dyn Mutate<T> {
+                    match index {
+                        $(
+                            $idx => &mut self.$idx,
+                        )*
+                        _ => panic!("mutator index out of bounds: {index}"),
+                    }
+                }
+            }
+
+            impl<T, $( $m , )*> Generators<T> for ( $( $m , )* )
+            where
+                $(
+                    $m: Generate<T>,
+                )*
+            {
+                fn generator(&mut self, index: usize) -> &mut dyn Generate<T> {
+                    match index {
+                        $(
+                            $idx => &mut self.$idx,
+                        )*
+                        _ => panic!("generator index out of bounds: {index}"),
+                    }
+                }
+            }
+        )*
+    };
+
+    (@count) => { 0 };
+    (@count $head:ident $( $rest:ident )*) => { 1 + tuple_mutators!(@count $( $rest )*) };
+}
+
+tuple_mutators! {
+    (0: M0,);
+    (0: M0, 1: M1,);
+    (0: M0, 1: M1, 2: M2,);
+    (0: M0, 1: M1, 2: M2, 3: M3,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5, 6: M6,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5, 6: M6, 7: M7,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5, 6: M6, 7: M7, 8: M8,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5, 6: M6, 7: M7, 8: M8, 9: M9,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5, 6: M6, 7: M7, 8: M8, 9: M9, 10: M10,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5, 6: M6, 7: M7, 8: M8, 9: M9, 10: M10, 11: M11,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5, 6: M6, 7: M7, 8: M8, 9: M9, 10: M10, 11: M11, 12: M12,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5, 6: M6, 7: M7, 8: M8, 9: M9, 10: M10, 11: M11, 12: M12, 13: M13,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5, 6: M6, 7: M7, 8: M8, 9: M9, 10: M10, 11: M11, 12: M12, 13: M13, 14: M14,);
+    (0: M0, 1: M1, 2: M2, 3: M3, 4: M4, 5: M5, 6: M6, 7: M7, 8: M8, 9: M9, 10: M10, 11: M11, 12: M12, 13: M13, 14: M14, 15: M15,);
+}
+
+/// Mutate a value with one of the given `mutators`, choosing the `i`th mutator
+/// with probability proportional to `weight(i)`.
+///
+/// Each mutator's candidates are registered as a weighted group, so that the
+/// mutators' weights are independent of how many candidates each mutator has,
+/// while every candidate can still be enumerated and scheduled by name.
+fn mutate_weighted<T, Ms>(
+    mutators: &mut Ms,
+    weight: impl Fn(usize) -> u32,
+    c: &mut Candidates,
+    value: &mut T,
+) -> Result<()>
+where
+    Ms: ?Sized + Mutators<T>,
+{
+    for i in 0..mutators.len() {
+        let mutator = mutators.mutator(i);
+        c.weighted_group(weight(i), |c| mutator.mutate(c, value))?;
+    }
+    Ok(())
+}
+
+/// Generate a value with one of the given `generators`, choosing the `i`th
+/// generator with probability proportional to `weight(i)`.
+fn generate_weighted<T, Gs>(
+    generators: &mut Gs,
+    weight: impl Fn(usize) -> u32,
+    ctx: &mut Context,
+) -> Result<T>
+where
+    Gs: ?Sized + Generators<T>,
+{
+    let total = (0..generators.len())
+        .map(|i| u64::from(weight(i)))
+        .sum::<u64>();
+    if total == 0 {
+        return Err(Error::exhausted());
+    }
+
+    let mut target = ctx.rng().gen_u64() % total;
+    let mut chosen = 0;
+    for i in 0..generators.len() {
+        let weight = u64::from(weight(i));
+        if target < weight {
+            chosen = i;
+            break;
+        }
+        target -= weight;
+    }
+
+    // If the chosen generator is exhausted, for example because it is a
+    // recursive generator that has reached the maximum depth, then fall back to
+    // the other generators in turn.
+    let len = generators.len();
+    for i in (chosen..len).chain(0..chosen) {
+        if weight(i) == 0 {
+            continue;
+        }
+        match generators.generator(i).generate(ctx) {
+            Err(e) if e.is_exhausted() => continue,
+            result => return result,
+        }
+    }
+    Err(Error::exhausted())
+}
+
+/// A mutator combinator for choosing among many mutators.
+///
+/// See the [`one_of`] function for details and example usage.
+#[derive(Clone, Debug, Default)]
+pub struct OneOf<Ms> {
+    pub(crate) mutators: Ms,
+}
+
+/// Create a mutator that mutates values with one of the given mutators, chosen
+/// uniformly at random.
+///
+/// The `mutators` may be a tuple of mutators of different types, an array of
+/// mutators, or (when the `alloc` feature is enabled) a vector of mutators.
+/// See the [`Mutators`] trait for details.
+///
+/// Unlike [`or`][Mutate::or], which combines all of its mutators' candidate
+/// mutations into one pool with equal weights, `one_of` divides each mutator's
+/// share of the weight among that mutator's candidate mutations. Therefore,
+/// each mutator is equally likely to be chosen, regardless of how many
+/// candidate mutations it has. Mutators that have no candidate mutations for a
+/// given value are never chosen. Together, each mutator's candidates are as
+/// likely to be chosen as a single candidate of a sibling mutator, for
+/// example when `one_of` is itself combined with another mutator via `or`.
+///
+/// If every mutator also implements [`Generate`], then so does the resulting
+/// mutator. When generating a value, if the chosen generator is exhausted, the
+/// other generators are tried in turn.
+///
+/// # Example
+///
+/// ```
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{mutators as m, Mutate, Session};
+///
+/// let mut mutator = m::one_of((
+///     m::just(0),
+///     m::just(u32::MAX),
+///     m::range(1..=10_u32),
+///     m::u32(),
+/// ));
+///
+/// let mut session = Session::new();
+///
+/// let mut x = 42;
+/// for _ in 0..5 {
+///     session.mutate_with(&mut mutator, &mut x)?;
+///     println!("mutated x is {x}");
+/// }
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+pub fn one_of<Ms>(mutators: Ms) -> OneOf<Ms> {
+    OneOf { mutators }
+}
+
+impl<Ms, T> Mutate<T> for OneOf<Ms>
+where
+    Ms: Mutators<T>,
+{
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        mutate_weighted(&mut self.mutators, |_| 1, c, value)
+    }
+}
+
+impl<Ms, T> Generate<T> for OneOf<Ms>
+where
+    Ms: Generators<T>,
+{
+    fn generate(&mut self, ctx: &mut Context) -> Result<T> {
+        generate_weighted(&mut self.mutators, |_| 1, ctx)
+    }
+}
+
+/// A mutator combinator for choosing among many mutators with given relative
+/// weights.
+///
+/// See the [`frequency`] function for details and example usage.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct Frequency<M> {
+    pub(crate) weights: alloc::vec::Vec<u32>,
+    pub(crate) mutators: alloc::vec::Vec<M>,
+}
+
+/// Create a mutator that mutates values with one of the given mutators, chosen
+/// at random with probability proportional to its weight.
+///
+/// This is like [`one_of`] but allows controlling how likely each mutator is to
+/// be chosen. For example, a mutator with weight `3` is chosen three times as
+/// often as a mutator with weight `1`. Mutators with weight `0` are never
+/// chosen, and neither are mutators that have no candidate mutations for a
+/// given value.
+///
+/// To choose among mutators of different types, box them with
+/// [`Mutate::boxed`].
+///
+/// If every mutator also implements [`Generate`], then so does the resulting
+/// mutator. When generating a value, if the chosen generator is exhausted, the
+/// other generators are tried in turn.
+///
+/// # Example
+///
+/// ```
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{mutators as m, Mutate, Session};
+///
+/// let mut mutator = m::frequency([
+///     // Mostly make small mutations...
+///     (9, m::range(0..=10).boxed()),
+///     // ...but occasionally make arbitrary mutations.
+///     (1, m::u32().boxed()),
+/// ]);
+///
+/// let mut session = Session::new();
+///
+/// let mut x = 5;
+/// for _ in 0..5 {
+///     session.mutate_with(&mut mutator, &mut x)?;
+///     println!("mutated x is {x}");
+/// }
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+#[cfg(feature = "alloc")]
+pub fn frequency<M>(choices: impl IntoIterator<Item = (u32, M)>) -> Frequency<M> {
+    let (weights, mutators) = choices.into_iter().unzip();
+    Frequency { weights, mutators }
+}
+
+#[cfg(feature = "alloc")]
+impl<M, T> Mutate<T> for Frequency<M>
+where
+    M: Mutate<T>,
+{
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        let weights = &self.weights;
+        mutate_weighted(&mut self.mutators, |i| weights[i], c, value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M, T> Generate<T> for Frequency<M>
+where
+    M: Generate<T>,
+{
+    fn generate(&mut self, ctx: &mut Context) -> Result<T> {
+        let weights = &self.weights;
+        generate_weighted(&mut self.mutators, |i| weights[i], ctx)
+    }
+}
+
+/// A mutator combinator for choosing which mutator to use based on the value
+/// being mutated.
+///
+/// See the [`bind`] function for details and example usage.
+#[derive(Clone, Debug)]
+pub struct Bind<F> {
+    pub(crate) f: F,
+}
+
+/// Create a mutator that inspects the whole value being mutated and then
+/// returns the mutator to use for it.
+///
+/// This is the mutator equivalent of `Option::and_then` and friends. It is
+/// useful when the mutation of one part of a value depends on another part,
+/// for example when one field must remain greater than another. The returned
+/// mutator will often use [`proj`][Mutate::proj] to focus on the dependent
+/// part of the value.
+///
+/// The function `f` may be called multiple times per mutation, and must return
+/// equivalent mutators each time it is given the same value.
+///
+/// # Example
+///
+/// ```
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{mutators as m, Mutate, Session};
+///
+/// #[derive(Debug)]
+/// struct Span {
+///     start: u32,
+///     end: u32,
+/// }
+///
+/// // Mutate the span's `end`, keeping it at or after the span's `start`.
+/// let mut mutator = m::bind(|span: &Span| {
+///     m::range(span.start..=u32::MAX).proj(|span: &mut Span| &mut span.end)
+/// });
+///
+/// let mut session = Session::new();
+///
+/// let mut span = Span { start: 10, end: 20 };
+/// for _ in 0..10 {
+///     session.mutate_with(&mut mutator, &mut span)?;
+///     assert!(span.start <= span.end);
+/// }
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+pub fn bind<F>(f: F) -> Bind<F> {
+    Bind { f }
+}
+
+impl<F, M, T> Mutate<T> for Bind<F>
+where
+    F: FnMut(&T) -> M,
+    M: Mutate<T>,
+{
+    #[inline]
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        let mut mutator = (self.f)(value);
+        mutator.mutate(c, value)
+    }
+}
+
+/// A mutator combinator for repairing values after they are mutated or
+/// generated.
+///
+/// See the [`fixup`] function for details and example usage.
+#[derive(Clone, Debug)]
+pub struct Fixup<M, F> {
+    pub(crate) mutator: M,
+    pub(crate) fix: F,
+}
+
+/// Create a mutator that applies the `fix` function to the value after every
+/// mutation that `mutator` applies, and after every value that it generates.
+///
+/// This is useful for restoring invariants that relate multiple parts of a
+/// value, such as a length field that must match the length of some data,
+/// after the parts are mutated independently.
+///
+/// This differs from [`map`][Mutate::map] in a few ways:
+///
+/// * `map`'s function only runs when the applied candidate mutation succeeds.
+///   `fix` runs after every candidate mutation that is applied, even one that
+///   fails after partially modifying the value, so that the invariant is
+///   restored regardless.
+///
+/// * `fix` is also applied to generated values: if `mutator` implements
+///   [`Generate`], then so does the resulting mutator.
+///
+/// * `fix` is infallible and does not have access to the mutation context
+///   (and therefore cannot itself make random choices).
+///
+/// Counting candidate mutations never modifies the value, so `fix` is not run
+/// then.
+///
+/// # Example
+///
+/// ```
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{mutators as m, Mutate, Session};
+///
+/// // A packet is a length and up to four bytes of data, where the length must
+/// // always be the number of non-zero data bytes.
+/// type Packet = (u8, [u8; 4]);
+///
+/// let mut mutator = m::fixup(m::default::<Packet>(), |(len, data): &mut Packet| {
+///     *len = data.iter().filter(|b| **b != 0).count() as u8;
+/// });
+///
+/// let mut session = Session::new();
+///
+/// let mut packet = (0, [0; 4]);
+/// for _ in 0..10 {
+///     session.mutate_with(&mut mutator, &mut packet)?;
+///     let (len, data) = packet;
+///     assert_eq!(usize::from(len), data.iter().filter(|b| **b != 0).count());
+/// }
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+pub fn fixup<M, F>(mutator: M, fix: F) -> Fixup<M, F> {
+    Fixup { mutator, fix }
+}
+
+impl<M, F, T> Mutate<T> for Fixup<M, F>
+where
+    M: Mutate<T>,
+    F: FnMut(&mut T),
+{
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        let result = self.mutator.mutate(c, value);
+        // If one of our mutator's candidates was applied, then it was the last
+        // candidate to run, whether it succeeded or not.
+        if c.applied_mutation {
+            (self.fix)(value);
+        }
+        result
+    }
+}
+
+impl<M, F, T> Generate<T> for Fixup<M, F>
+where
+    M: Generate<T>,
+    F: FnMut(&mut T),
+{
+    fn generate(&mut self, ctx: &mut Context) -> Result<T> {
+        let mut value = self.mutator.generate(ctx)?;
+        (self.fix)(&mut value);
+        Ok(value)
+    }
+
+    fn generated_size(&self, value: &T) -> core::option::Option<usize> {
+        self.mutator.generated_size(value)
+    }
+
+    fn min_generated_size(&self) -> core::option::Option<usize> {
+        self.mutator.min_generated_size()
+    }
+}
+
+/// A generator combinator for estimating the size of generated values with
+/// their [`SizeHint`] implementation.
+///
+/// See the [`size_hinted`] function for details and example usage.
+#[derive(Clone, Debug, Default)]
+pub struct SizeHinted<M> {
+    pub(crate) mutator: M,
+}
+
+/// Create a generator that estimates the size of the values that `mutator`
+/// generates with their [`SizeHint`] implementation.
+///
+/// Mutators that generate new sub-values, such as [`option`] and [`result`],
+/// check the size of those values against the session's
+/// [maximum size][crate::Session::max_size]. By default, the size of a
+/// generated value is assumed to be the in-memory size of its type, which
+/// underestimates values that own heap data, like `String`s and `Vec`s. Wrap
+/// their generators with `size_hinted` to measure them instead.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{mutators as m, Candidates, Context, Generate, Mutate, Result, Session, SizeHint};
+///
+/// /// A generator of strings of `'a'`s.
+/// struct As;
+///
+/// impl Mutate<String> for As {
+///     fn mutate(&mut self, c: &mut Candidates, s: &mut String) -> Result<()> {
+///         c.mutation(|_| Ok(s.push('a')))
+///     }
+/// }
+///
+/// impl Generate<String> for As {
+///     fn generate(&mut self, ctx: &mut Context) -> Result<String> {
+///         let len = ctx.rng().gen_index(100).unwrap();
+///         Ok("a".repeat(len))
+///     }
+/// }
+///
+/// let mut mutator = m::option(m::size_hinted(As));
+/// let mut session = Session::new().max_size(8);
+///
+/// for _ in 0..10 {
+///     let mut value = None;
+///     let _ = session.mutate_with(&mut mutator, &mut value);
+///     assert!(value.size_hint() <= 8);
+/// }
+/// # Ok(())
+/// # }
+/// # #[cfg(feature = "alloc")]
+/// # foo().unwrap();
+/// ```
+pub fn size_hinted<M>(mutator: M) -> SizeHinted<M> {
+    SizeHinted { mutator }
+}
+
+impl<M, T> Mutate<T> for SizeHinted<M>
+where
+    M: Mutate<T>,
+{
+    #[inline]
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        self.mutator.mutate(c, value)
+    }
+}
+
+impl<M, T> Generate<T> for SizeHinted<M>
+where
+    M: Generate<T>,
+    T: SizeHint,
+{
+    #[inline]
+    fn generate(&mut self, ctx: &mut Context) -> Result<T> {
+        self.mutator.generate(ctx)
+    }
+
+    #[inline]
+    fn generated_size(&self, value: &T) -> core::option::Option<usize> {
+        Some(value.size_hint())
+    }
+
+    #[inline]
+    fn min_generated_size(&self) -> core::option::Option<usize> {
+        Some(0)
+    }
+}
+
+/// A mutator combinator for lazily constructing a mutator on first use.
+///
+/// See the [`lazy`] function for details and example usage.
+#[derive(Clone, Debug)]
+pub struct Lazy<M, F> {
+    pub(crate) make: F,
+    pub(crate) mutator: core::option::Option<M>,
+}
+
+/// Create a mutator that lazily constructs its inner mutator, by calling
+/// `make`, the first time it is used.
+///
+/// This is the building block for mutators of recursive types: a mutator
+/// cannot contain itself, but it can contain a lazy mutator that creates
+/// another instance of itself on demand. Because the type of such a mutator
+/// would otherwise be infinitely large, `make` will generally return a boxed
+/// mutator (see [`Mutate::boxed`]).
+///
+/// Each time a `Lazy` mutator delegates to its inner mutator, it increments
+/// the context's recursion [`depth`][Context::depth]. Once the
+/// [maximum depth][crate::Session::max_depth] is reached, it registers no
+/// candidate mutations and fails to generate values, which stops the
+/// recursion. If the inner mutator implements [`Generate`], then so does the
+/// lazy mutator.
+///
+/// See also the [`recursive`] combinator, which avoids the need to define a
+/// named function for the recursive mutator.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{mutators as m, Candidates, DynMutate, Mutate, Result, Session};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum List {
+///     Nil,
+///     Cons(u32, Box<List>),
+/// }
+///
+/// /// A mutator for the elements of a `List`, using `M` for the list's tail.
+/// struct Elems<M>(M);
+///
+/// impl<M: Mutate<List>> Mutate<List> for Elems<M> {
+///     fn mutate(&mut self, c: &mut Candidates, list: &mut List) -> Result<()> {
+///         if let List::Cons(x, tail) = list {
+///             m::u32().mutate(c, x)?;
+///             self.0.mutate(c, tail)?;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// fn elems() -> Box<dyn DynMutate<List>> {
+///     Elems(m::lazy(elems)).boxed()
+/// }
+///
+/// let mut mutator = elems();
+///
+/// // Only mutate the first two elements of the list.
+/// let mut session = Session::new().max_depth(1);
+///
+/// let mut list = List::Cons(1, Box::new(List::Cons(2, Box::new(List::Cons(3, Box::new(List::Nil))))));
+/// for _ in 0..10 {
+///     session.mutate_with(&mut mutator, &mut list)?;
+/// }
+///
+/// let List::Cons(_, tail) = &list else { unreachable!() };
+/// let List::Cons(_, tail) = &**tail else { unreachable!() };
+/// assert_eq!(**tail, List::Cons(3, Box::new(List::Nil)));
+/// # Ok(())
+/// # }
+/// # #[cfg(feature = "alloc")]
+/// # foo().unwrap();
+/// ```
+pub fn lazy<M, F>(make: F) -> Lazy<M, F>
+where
+    F: FnMut() -> M,
+{
+    Lazy {
+        make,
+        mutator: None,
+    }
+}
+
+impl<M, F, T> Mutate<T> for Lazy<M, F>
+where
+    F: FnMut() -> M,
+    M: Mutate<T>,
+{
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        let make = &mut self.make;
+        let mutator = &mut self.mutator;
+        c.nested(|c| mutator.get_or_insert_with(make).mutate(c, value))
+            .unwrap_or(Ok(()))
+    }
+}
+
+impl<M, F, T> Generate<T> for Lazy<M, F>
+where
+    F: FnMut() -> M,
+    M: Generate<T>,
+{
+    fn generate(&mut self, ctx: &mut Context) -> Result<T> {
+        let make = &mut self.make;
+        let mutator = &mut self.mutator;
+        ctx.nested(|ctx| mutator.get_or_insert_with(make).generate(ctx))
+            .unwrap_or_else(|| Err(Error::exhausted()))
+    }
+
+    fn generated_size(&self, value: &T) -> core::option::Option<usize> {
+        self.mutator.as_ref()?.generated_size(value)
+    }
+
+    fn min_generated_size(&self) -> core::option::Option<usize> {
+        self.mutator.as_ref()?.min_generated_size()
+    }
+}
+
+/// A lazily-constructed default mutator.
+///
+/// This is the default mutator for `Box<T>` values, wrapped in
+/// [`m::Box`][Box], and it is what allows deriving `Mutate` for recursive
+/// types. See the [`lazy_default`] function for more details.
+#[cfg(feature = "alloc")]
+pub struct LazyDefault<T>
+where
+    T: DefaultMutate,
+{
+    pub(crate) mutator: core::option::Option<alloc::boxed::Box<T::DefaultMutate>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Clone for LazyDefault<T>
+where
+    T: DefaultMutate,
+    T::DefaultMutate: Clone,
+{
+    fn clone(&self) -> Self {
+        LazyDefault {
+            mutator: self.mutator.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::fmt::Debug for LazyDefault<T>
+where
+    T: DefaultMutate,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LazyDefault")
+            .field("initialized", &self.mutator.is_some())
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Default for LazyDefault<T>
+where
+    T: DefaultMutate,
+{
+    fn default() -> Self {
+        LazyDefault { mutator: None }
+    }
+}
+
+/// Create a mutator that lazily constructs `T`'s default mutator the first
+/// time it is used.
+///
+/// This is like [`lazy`], but it is named by the type `T` rather than by the
+/// type of its inner mutator. That is what allows it to be used inside `T`'s
+/// default mutator itself, for example in the derived `Mutate` implementation
+/// of a recursive type, without creating an infinitely large type.
+///
+/// Like [`lazy`], this increments the context's recursion
+/// [`depth`][Context::depth], and registers no mutations at the
+/// [maximum depth][crate::Session::max_depth].
+///
+/// # Example
+///
+/// ```
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{mutators as m, Session};
+///
+/// let mut mutator = m::lazy_default::<u32>();
+///
+/// let mut session = Session::new();
+/// let mut value = 42;
+/// session.mutate_with(&mut mutator, &mut value)?;
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+#[cfg(feature = "alloc")]
+pub fn lazy_default<T>() -> LazyDefault<T>
+where
+    T: DefaultMutate,
+{
+    LazyDefault::default()
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Mutate<T> for LazyDefault<T>
+where
+    T: DefaultMutate,
+{
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        let mutator = &mut self.mutator;
+        c.nested(|c| {
+            mutator
+                .get_or_insert_with(|| alloc::boxed::Box::new(default::<T>()))
+                .mutate(c, value)
+        })
+        .unwrap_or(Ok(()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Generate<T> for LazyDefault<T>
+where
+    T: DefaultMutate,
+    T::DefaultMutate: Generate<T>,
+{
+    fn generate(&mut self, ctx: &mut Context) -> Result<T> {
+        let mutator = &mut self.mutator;
+        ctx.nested(|ctx| {
+            mutator
+                .get_or_insert_with(|| alloc::boxed::Box::new(default::<T>()))
+                .generate(ctx)
+        })
+        .unwrap_or_else(|| Err(Error::exhausted()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+type MakeRecursive<T> = alloc::sync::Arc<
+    dyn Fn(Recursive<T>) -> alloc::boxed::Box<dyn Generate<T> + Send> + Send + Sync,
+>;
+
+/// A mutator combinator for recursive types.
+///
+/// See the [`recursive`] function for details and example usage.
+#[cfg(feature = "alloc")]
+pub struct Recursive<T>
+where
+    T: 'static,
+{
+    make: MakeRecursive<T>,
+    mutator: core::option::Option<alloc::boxed::Box<dyn Generate<T> + Send>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Clone for Recursive<T> {
+    /// Cloning a `Recursive` mutator creates a new, uninitialized handle to the
+    /// same recursive mutator definition.
+    fn clone(&self) -> Self {
+        Recursive {
+            make: self.make.clone(),
+            mutator: None,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::fmt::Debug for Recursive<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Recursive")
+            .field("initialized", &self.mutator.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Create a mutator and generator for a recursive type.
+///
+/// The function `f` is given a handle to the recursive mutator itself, which
+/// it can use for the recursive parts of the value, and returns the mutator
+/// for the whole value. The resulting mutator must implement [`Generate`].
+///
+/// Every time the recursive mutator is nested inside itself, it increments the
+/// context's recursion [`depth`][Context::depth], and once it reaches the
+/// [maximum depth][crate::Session::max_depth] it registers no candidate
+/// mutations and fails to generate values. Combined with a non-recursive base
+/// case inside a [`one_of`] or [`frequency`] combinator, which fall back to
+/// their other generators when the chosen one fails, this ensures that
+/// generating recursive values terminates.
+///
+/// To define mutators for recursive types that do not support generation, use
+/// the [`lazy`] combinator instead.
+///
+/// So that the resulting mutator is `Send`, both `f` and the mutators it
+/// returns must be `Send`, and `f` must also be `Sync`.
+///
+/// # Example
+///
+/// ```
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{mutators as m, Candidates, Context, Generate, Mutate, Result, Session};
+///
+/// #[derive(Debug)]
+/// enum Expr {
+///     Lit(u8),
+///     Add(Box<Expr>, Box<Expr>),
+/// }
+///
+/// /// A mutator for `Expr::Lit` expressions.
+/// struct Lit;
+///
+/// impl Mutate<Expr> for Lit {
+///     fn mutate(&mut self, c: &mut Candidates, expr: &mut Expr) -> Result<()> {
+///         match expr {
+///             Expr::Lit(x) => m::u8().mutate(c, x),
+///             _ => Ok(()),
+///         }
+///     }
+/// }
+///
+/// impl Generate<Expr> for Lit {
+///     fn generate(&mut self, ctx: &mut Context) -> Result<Expr> {
+///         Ok(Expr::Lit(m::u8().generate(ctx)?))
+///     }
+/// }
+///
+/// /// A mutator for `Expr::Add` expressions, using `M` for the operands.
+/// struct Add<M>(M);
+///
+/// impl<M: Mutate<Expr>> Mutate<Expr> for Add<M> {
+///     fn mutate(&mut self, c: &mut Candidates, expr: &mut Expr) -> Result<()> {
+///         match expr {
+///             Expr::Add(a, b) => {
+///                 self.0.mutate(c, a)?;
+///                 self.0.mutate(c, b)
+///             }
+///             _ => Ok(()),
+///         }
+///     }
+/// }
+///
+/// impl<M: Generate<Expr>> Generate<Expr> for Add<M> {
+///     fn generate(&mut self, ctx: &mut Context) -> Result<Expr> {
+///         let a = self.0.generate(ctx)?;
+///         let b = self.0.generate(ctx)?;
+///         Ok(Expr::Add(Box::new(a), Box::new(b)))
+///     }
+/// }
+///
+/// // A mutator for arbitrarily nested `Expr`s.
+/// let mut mutator = m::recursive(|expr| m::one_of((Lit, Add(expr))));
+///
+/// let mut session = Session::new().max_depth(8);
+/// let mut expr = Expr::Add(Box::new(Expr::Lit(1)), Box::new(Expr::Lit(2)));
+/// for _ in 0..10 {
+///     session.mutate_with(&mut mutator, &mut expr)?;
+///     println!("mutated expr is {expr:?}");
+/// }
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+#[cfg(feature = "alloc")]
+pub fn recursive<T, F, M>(f: F) -> Recursive<T>
+where
+    F: Fn(Recursive<T>) -> M + Send + Sync + 'static,
+    M: Generate<T> + Send + 'static,
+{
+    Recursive {
+        make: alloc::sync::Arc::new(move |handle| alloc::boxed::Box::new(f(handle))),
+        mutator: None,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Mutate<T> for Recursive<T> {
+    fn mutate(&mut self, c: &mut Candidates, value: &mut T) -> Result<()> {
+        let make = &self.make;
+        let mutator = &mut self.mutator;
+        c.nested(|c| {
+            mutator
+                .get_or_insert_with(|| {
+                    make(Recursive {
+                        make: make.clone(),
+                        mutator: None,
+                    })
+                })
+                .mutate(c, value)
+        })
+        .unwrap_or(Ok(()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Generate<T> for Recursive<T> {
+    fn generate(&mut self, ctx: &mut Context) -> Result<T> {
+        let make = &self.make;
+        let mutator = &mut self.mutator;
+        ctx.nested(|ctx| {
+            mutator
+                .get_or_insert_with(|| {
+                    make(Recursive {
+                        make: make.clone(),
+                        mutator: None,
+                    })
+                })
+                .generate(ctx)
+        })
+        .unwrap_or_else(|| Err(Error::exhausted()))
+    }
+}
+
 /// A mutator combinator for projecting a value to a sub-value and applying a
 /// mutator to that sub-value.
 ///
@@ -123,7 +1275,7 @@ where
 {
     #[inline]
     fn mutate(&mut self, c: &mut Candidates<'_>, value: &mut T) -> Result<()> {
-        c.mutation(|_| Ok(*value = self.value.clone()))
+        c.named_mutation("just", |_| Ok(*value = self.value.clone()))
     }
 }
 
@@ -135,3 +1287,83 @@ where
         Ok(self.value.clone())
     }
 }
+
+/// A mutator that chooses values from a fixed dictionary.
+///
+/// See the [`dictionary`] function for more information.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct Dictionary<T> {
+    pub(crate) values: alloc::vec::Vec<T>,
+}
+
+/// Create a mutator that replaces values with a random choice from the given
+/// dictionary of values.
+///
+/// This is useful for injecting "magic" values, such as keywords, magic
+/// numbers, and protocol tokens, that are unlikely to be produced by random
+/// mutation alone. It is like [`just`], but for many values. It is often
+/// combined with another mutator via [`or`][Mutate::or] or [`frequency`].
+///
+/// When shrinking, this mutator does not register any mutations, since it
+/// cannot tell whether a dictionary value is smaller than the original value.
+///
+/// To splice raw byte tokens into the built-in mutators instead, see
+/// [`Session::dict`][crate::Session::dict].
+///
+/// # Example
+///
+/// ```
+/// # fn foo() -> mutatis::Result<()> {
+/// use mutatis::{mutators as m, Mutate, Session};
+///
+/// let keywords = ["SELECT", "FROM", "WHERE"];
+/// let mut mutator = m::dictionary(keywords);
+///
+/// let mut session = Session::new();
+///
+/// let mut keyword = "SELECT";
+/// for _ in 0..5 {
+///     session.mutate_with(&mut mutator, &mut keyword)?;
+///     assert!(keywords.contains(&keyword));
+/// }
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+#[cfg(feature = "alloc")]
+pub fn dictionary<T>(values: impl IntoIterator<Item = T>) -> Dictionary<T> {
+    Dictionary {
+        values: values.into_iter().collect(),
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Mutate<T> for Dictionary<T>
+where
+    T: Clone,
+{
+    #[inline]
+    fn mutate(&mut self, c: &mut Candidates<'_>, value: &mut T) -> Result<()> {
+        if c.shrink() || self.values.is_empty() {
+            return Ok(());
+        }
+        c.named_mutation("dictionary", |ctx| {
+            *value = ctx.rng().choose(&self.values).unwrap().clone();
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Generate<T> for Dictionary<T>
+where
+    T: Clone,
+{
+    fn generate(&mut self, ctx: &mut Context) -> Result<T> {
+        match ctx.rng().choose(&self.values) {
+            Some(v) => Ok(v.clone()),
+            None => Err(Error::exhausted()),
+        }
+    }
+}
@@ -38,7 +38,7 @@ impl Mutate<bool> for Bool {
     #[inline]
     fn mutate(&mut self, c: &mut Candidates, value: &mut bool) -> Result<()> {
         if !c.shrink() || *value {
-            c.mutation(|_ctx| Ok(*value = !*value))?;
+            c.named_mutation("bool.flip", |_ctx| Ok(*value = !*value))?;
         }
         Ok(())
     }
@@ -48,6 +48,10 @@ impl DefaultMutate for bool {
     type DefaultMutate = Bool;
 }
 
+/// The maximum amount by which the integer mutators' arithmetic candidates
+/// increment or decrement a value, matching AFL's `ARITH_MAX`.
+const ARITH_MAX: u8 = 35;
+
 macro_rules! ints {
     (
         $(
@@ -96,7 +100,105 @@ macro_rules! ints {
                     if c.shrink() && *value == 0 {
                         return Ok(());
                     }
-                    c.mutation(|ctx| {
+
+                    // Splice in a dictionary token of the same width, if
+                    // there are any.
+                    #[cfg(feature = "alloc")]
+                    if !c.shrink()
+                        && c.context.dict().count_of_len(core::mem::size_of::<$ty>()) > 0
+                    {
+                        c.named_mutation("int.dict", |ctx| {
+                            let token = ctx.choose_token().unwrap();
+                            *value = if ctx.rng().gen_bool() {
+                                <$ty>::from_le_bytes(token)
+                            } else {
+                                <$ty>::from_be_bytes(token)
+                            };
+                            Ok(())
+                        })?;
+                    }
+
+                    // Replace the value with an operand of a comparison
+                    // that the program under test performed, if there are
+                    // any of the same width.
+                    #[cfg(feature = "alloc")]
+                    if !c.shrink()
+                        && c.context.cmp_log().count_of_width(core::mem::size_of::<$ty>()) > 0
+                    {
+                        c.named_mutation("int.cmp", |ctx| {
+                            let operand = ctx
+                                .choose_cmp_operand(core::mem::size_of::<$ty>(), *value as u64)
+                                .unwrap();
+                            *value = operand as $ty;
+                            Ok(())
+                        })?;
+                    }
+
+                    if !c.shrink() {
+                        // Small increments and decrements, to step across
+                        // off-by-one boundaries.
+                        c.named_mutation("int.arith", |ctx| {
+                            let delta = ctx.rng().inner().gen_range(1..=ARITH_MAX) as $ty;
+                            *value = if ctx.rng().gen_bool() {
+                                value.wrapping_add(delta)
+                            } else {
+                                value.wrapping_sub(delta)
+                            };
+                            Ok(())
+                        })?;
+
+                        // Flip a single bit.
+                        c.named_mutation("int.bitflip", |ctx| {
+                            let bit = ctx.rng().gen_index(<$ty>::BITS as usize).unwrap();
+                            let one: $ty = 1;
+                            *value ^= one << bit;
+                            Ok(())
+                        })?;
+
+                        // Swap the value's endianness.
+                        if value.swap_bytes() != *value {
+                            c.named_mutation("int.swap_bytes", |_| {
+                                *value = value.swap_bytes();
+                                Ok(())
+                            })?;
+                        }
+
+                        // Flip the value's sign (or, for unsigned integers,
+                        // take its two's complement).
+                        if value.wrapping_neg() != *value {
+                            c.named_mutation("int.negate", |_| {
+                                *value = value.wrapping_neg();
+                                Ok(())
+                            })?;
+                        }
+
+                        // Replace the value with an interesting boundary
+                        // constant.
+                        c.named_mutation("int.interesting", |ctx| {
+                            let one: $ty = 1;
+                            *value = match ctx.rng().gen_index(9).unwrap() {
+                                0 => 0,
+                                1 => 1,
+                                2 => !0,
+                                3 => <$ty>::MIN,
+                                4 => <$ty>::MIN.wrapping_add(1),
+                                5 => <$ty>::MAX,
+                                6 => <$ty>::MAX.wrapping_sub(1),
+                                i => {
+                                    let bit = ctx.rng().gen_index(<$ty>::BITS as usize).unwrap();
+                                    let pow2 = one << bit;
+                                    if i == 7 {
+                                        pow2
+                                    } else {
+                                        pow2.wrapping_sub(1)
+                                    }
+                                }
+                            };
+                            Ok(())
+                        })?;
+                    }
+
+                    c.named_mutation("int.random", |ctx| {
                         *value = if ctx.shrink() {
                             ctx.rng().inner().gen_range(0..*value)
                         } else {
@@ -137,7 +239,7 @@ macro_rules! ints {
                         return Ok(());
                     }
 
-                    c.mutation(|ctx| {
+                    c.named_mutation("int.range", |ctx| {
                         let end = if ctx.shrink() {
                             cmp::min(*value, end)
                         } else {
@@ -213,7 +315,7 @@ impl Mutate<char> for Char {
     fn mutate(&mut self, c: &mut Candidates, value: &mut char) -> Result<()> {
         if c.shrink() {
             if *value != '\0' {
-                c.mutation(|ctx| {
+                c.named_mutation("char.shrink", |ctx| {
                     *value = ctx.rng().inner().gen_range('\0'..*value);
                     Ok(())
                 })?;
@@ -255,7 +357,7 @@ impl Mutate<char> for Char {
 
             // Catch all: any valid character, regardless of its plane, block,
             // or if it has been assigned or not.
-            c.mutation(|ctx| Ok(*value = ctx.rng().inner().gen()))?;
+            c.named_mutation("char.random", |ctx| Ok(*value = ctx.rng().inner().gen()))?;
 
             Ok(())
         }
@@ -292,7 +394,7 @@ impl MutateInRange<char> for Char {
             return Ok(());
         }
 
-        c.mutation(|ctx| {
+        c.named_mutation("char.range", |ctx| {
             let end = if ctx.shrink() {
                 core::cmp::min(*value, end)
             } else {
@@ -347,13 +449,13 @@ impl Mutate<f32> for F32 {
     #[inline]
     fn mutate(&mut self, c: &mut Candidates, value: &mut f32) -> Result<()> {
         let special_finite = |c: &mut Candidates, value: &mut f32| -> Result<()> {
-            c.mutation(|_| Ok(*value = 0.0))?;
-            c.mutation(|_| Ok(*value = 1.0))?;
-            c.mutation(|_| Ok(*value = -1.0))?;
-            c.mutation(|_| Ok(*value = f32::EPSILON))?;
-            c.mutation(|_| Ok(*value = f32::MIN_POSITIVE))?;
-            c.mutation(|_| Ok(*value = f32::MAX))?;
-            c.mutation(|_| Ok(*value = f32::MIN))?;
+            c.named_mutation("float.special", |_| Ok(*value = 0.0))?;
+            c.named_mutation("float.special", |_| Ok(*value = 1.0))?;
+            c.named_mutation("float.special", |_| Ok(*value = -1.0))?;
+            c.named_mutation("float.special", |_| Ok(*value = f32::EPSILON))?;
+            c.named_mutation("float.special", |_| Ok(*value = f32::MIN_POSITIVE))?;
+            c.named_mutation("float.special", |_| Ok(*value = f32::MAX))?;
+            c.named_mutation("float.special", |_| Ok(*value = f32::MIN))?;
             Ok(())
         };
 
@@ -361,10 +463,14 @@ impl Mutate<f32> for F32 {
             special_finite(c, value)?;
 
             // Positives.
-            c.mutation(|ctx| Ok(*value = ctx.rng().inner().gen::<f32>() * f32::MAX))?;
+            c.named_mutation("float.random", |ctx| {
+                Ok(*value = ctx.rng().inner().gen::<f32>() * f32::MAX)
+            })?;
 
             // Negatives.
-            c.mutation(|ctx| Ok(*value = ctx.rng().inner().gen::<f32>() * f32::MIN))?;
+            c.named_mutation("float.random", |ctx| {
+                Ok(*value = ctx.rng().inner().gen::<f32>() * f32::MIN)
+            })?;
 
             Ok(())
         };
@@ -376,13 +482,15 @@ impl Mutate<f32> for F32 {
             if value.is_nan() || value.is_infinite() {
                 return finite(c, value);
             }
-            c.mutation(|ctx| Ok(*value *= ctx.rng().inner().gen::<f32>()))?;
+            c.named_mutation("float.shrink", |ctx| {
+                Ok(*value *= ctx.rng().inner().gen::<f32>())
+            })?;
             Ok(())
         } else {
             finite(c, value)?;
-            c.mutation(|_| Ok(*value = f32::INFINITY))?;
-            c.mutation(|_| Ok(*value = f32::NEG_INFINITY))?;
-            c.mutation(|_| Ok(*value = f32::NAN))?;
+            c.named_mutation("float.non_finite", |_| Ok(*value = f32::INFINITY))?;
+            c.named_mutation("float.non_finite", |_| Ok(*value = f32::NEG_INFINITY))?;
+            c.named_mutation("float.non_finite", |_| Ok(*value = f32::NAN))?;
             Ok(())
         }
     }
@@ -431,13 +539,13 @@ impl Mutate<f64> for F64 {
     #[inline]
     fn mutate(&mut self, c: &mut Candidates, value: &mut f64) -> Result<()> {
         let special_finite = |c: &mut Candidates, value: &mut f64| -> Result<()> {
-            c.mutation(|_| Ok(*value = 0.0))?;
-            c.mutation(|_| Ok(*value = 1.0))?;
-            c.mutation(|_| Ok(*value = -1.0))?;
-            c.mutation(|_| Ok(*value = f64::EPSILON))?;
-            c.mutation(|_| Ok(*value = f64::MIN_POSITIVE))?;
-            c.mutation(|_| Ok(*value = f64::MAX))?;
-            c.mutation(|_| Ok(*value = f64::MIN))?;
+            c.named_mutation("float.special", |_| Ok(*value = 0.0))?;
+            c.named_mutation("float.special", |_| Ok(*value = 1.0))?;
+            c.named_mutation("float.special", |_| Ok(*value = -1.0))?;
+            c.named_mutation("float.special", |_| Ok(*value = f64::EPSILON))?;
+            c.named_mutation("float.special", |_| Ok(*value = f64::MIN_POSITIVE))?;
+            c.named_mutation("float.special", |_| Ok(*value = f64::MAX))?;
+            c.named_mutation("float.special", |_| Ok(*value = f64::MIN))?;
             Ok(())
         };
 
@@ -445,10 +553,14 @@ impl Mutate<f64> for F64 {
             special_finite(c, value)?;
 
             // Positives.
-            c.mutation(|ctx| Ok(*value = ctx.rng().inner().gen::<f64>() * f64::MAX))?;
+            c.named_mutation("float.random", |ctx| {
+                Ok(*value = ctx.rng().inner().gen::<f64>() * f64::MAX)
+            })?;
 
             // Negatives.
-            c.mutation(|ctx| Ok(*value = ctx.rng().inner().gen::<f64>() * f64::MIN))?;
+            c.named_mutation("float.random", |ctx| {
+                Ok(*value = ctx.rng().inner().gen::<f64>() * f64::MIN)
+            })?;
 
             Ok(())
         };
@@ -460,13 +572,15 @@ impl Mutate<f64> for F64 {
             if value.is_nan() || value.is_infinite() {
                 return finite(c, value);
             }
-            c.mutation(|ctx| Ok(*value *= ctx.rng().inner().gen::<f64>()))?;
+            c.named_mutation("float.shrink", |ctx| {
+                Ok(*value *= ctx.rng().inner().gen::<f64>())
+            })?;
             Ok(())
         } else {
             finite(c, value)?;
-            c.mutation(|_| Ok(*value = f64::INFINITY))?;
-            c.mutation(|_| Ok(*value = f64::NEG_INFINITY))?;
-            c.mutation(|_| Ok(*value = f64::NAN))?;
+            c.named_mutation("float.non_finite", |_| Ok(*value = f64::INFINITY))?;
+            c.named_mutation("float.non_finite", |_| Ok(*value = f64::NEG_INFINITY))?;
+            c.named_mutation("float.non_finite", |_| Ok(*value = f64::NAN))?;
             Ok(())
         }
     }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::log;
 
 /// A mutator for `Option<T>` values.
 ///
@@ -11,6 +12,10 @@ pub struct Option<M> {
 
 /// Create a new mutator for `Option<T>` values.
 ///
+/// Values are only changed from `None` to `Some` when the generated value fits
+/// within the session's [maximum size][crate::Session::max_size], as estimated
+/// by the inner generator's [`generated_size`][Generate::generated_size].
+///
 /// # Example
 ///
 /// ```
@@ -39,10 +44,14 @@ where
         }
 
         match value.as_mut() {
-            None => c.mutation(|ctx| Ok(*value = Some(self.mutator.generate(ctx)?))),
+            None if !may_fit_budget(&self.mutator, c, 0) => Ok(()),
+            None => c.named_mutation("option.some", |ctx| {
+                *value = Some(generate_within_budget(&mut self.mutator, ctx, 0)?);
+                Ok(())
+            }),
             Some(v) => {
                 self.mutator.mutate(c, v)?;
-                c.mutation(|_| Ok(*value = None))
+                c.named_mutation("option.none", |_| Ok(*value = None))
             }
         }
     }
@@ -67,6 +76,10 @@ pub struct Some<M> {
 /// Create a new mutator for `Option<T>` values that always produces `Some`
 /// values.
 ///
+/// Values are only changed from `None` to `Some` when the generated value fits
+/// within the session's [maximum size][crate::Session::max_size], as estimated
+/// by the inner generator's [`generated_size`][Generate::generated_size].
+///
 /// # Example
 ///
 /// ```
@@ -96,8 +109,11 @@ where
     #[inline]
     fn mutate(&mut self, c: &mut Candidates, value: &mut core::option::Option<T>) -> Result<()> {
         match value.as_mut() {
-            None if c.shrink() => Ok(()),
-            None => c.mutation(|ctx| Ok(*value = Some(self.mutator.generate(ctx)?))),
+            None if c.shrink() || !may_fit_budget(&self.mutator, c, 0) => Ok(()),
+            None => c.named_mutation("option.some", |ctx| {
+                *value = Some(generate_within_budget(&mut self.mutator, ctx, 0)?);
+                Ok(())
+            }),
             Some(v) => self.mutator.mutate(c, v),
         }
     }
@@ -139,8 +155,57 @@ impl<T> Mutate<core::option::Option<T>> for None {
     #[inline]
     fn mutate(&mut self, c: &mut Candidates, value: &mut core::option::Option<T>) -> Result<()> {
         if value.is_some() {
-            c.mutation(|_| Ok(*value = None))?;
+            c.named_mutation("option.none", |_| Ok(*value = None))?;
         }
         Ok(())
     }
 }
+
+/// Estimate the size of a value that `generator` generated, falling back to
+/// the in-memory size of `T` when the generator does not know it.
+pub(crate) fn generated_size<T>(generator: &impl Generate<T>, value: &T) -> usize {
+    generator
+        .generated_size(value)
+        .unwrap_or(core::mem::size_of::<T>())
+}
+
+/// Whether the smallest value that `generator` may generate, as estimated by
+/// its [`min_generated_size`][Generate::min_generated_size], fits within the
+/// remaining [size budget][crate::Session::max_size] when it replaces an
+/// existing sub-value whose size is `freed`.
+///
+/// Candidates that generate new sub-values are only registered when this
+/// holds.
+pub(crate) fn may_fit_budget<T>(
+    generator: &impl Generate<T>,
+    c: &Candidates,
+    freed: usize,
+) -> bool {
+    let min_size = generator
+        .min_generated_size()
+        .unwrap_or(core::mem::size_of::<T>());
+    c.can_grow(min_size.saturating_sub(freed))
+}
+
+/// Generate a new value that fits within the context's remaining
+/// [size budget][crate::Session::max_size], as estimated by the generator's
+/// [`generated_size`][Generate::generated_size], when it replaces an existing
+/// sub-value whose size is `freed`.
+///
+/// Candidates are only registered when [the smallest value that the generator
+/// may generate][may_fit_budget] fits, but the generated value may still be
+/// larger than that, in which case this reports exhaustion without changing
+/// anything.
+pub(crate) fn generate_within_budget<T>(
+    generator: &mut impl Generate<T>,
+    ctx: &mut Context,
+    freed: usize,
+) -> Result<T> {
+    let value = generator.generate(ctx)?;
+    if ctx.can_grow(generated_size(generator, &value).saturating_sub(freed)) {
+        Ok(value)
+    } else {
+        log::trace!("generated value exceeds the maximum size");
+        Err(Error::exhausted())
+    }
+}
@@ -12,6 +12,11 @@ pub struct Result<M, N> {
 
 /// Create a new `Result` mutator.
 ///
+/// Values are only changed from one variant to the other when the generated
+/// value fits within the session's [maximum size][crate::Session::max_size],
+/// as estimated by the inner generators'
+/// [`generated_size`][Generate::generated_size].
+///
 /// # Example
 ///
 /// ```
@@ -46,13 +51,23 @@ where
         match value {
             Ok(x) => {
                 self.ok_mutator.mutate(c, x)?;
-                if !c.shrink() {
-                    c.mutation(|ctx| Ok(*value = Err(self.err_mutator.generate(ctx)?)))?;
+                let freed = generated_size(&self.ok_mutator, x);
+                if !c.shrink() && may_fit_budget(&self.err_mutator, c, freed) {
+                    c.named_mutation("result.err", |ctx| {
+                        *value = Err(generate_within_budget(&mut self.err_mutator, ctx, freed)?);
+                        Ok(())
+                    })?;
                 }
             }
             Err(e) => {
                 self.err_mutator.mutate(c, e)?;
-                c.mutation(|ctx| Ok(*value = Ok(self.ok_mutator.generate(ctx)?)))?;
+                let freed = generated_size(&self.err_mutator, e);
+                if may_fit_budget(&self.ok_mutator, c, freed) {
+                    c.named_mutation("result.ok", |ctx| {
+                        *value = Ok(generate_within_budget(&mut self.ok_mutator, ctx, freed)?);
+                        Ok(())
+                    })?;
+                }
             }
         }
         Ok(())
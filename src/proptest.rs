@@ -0,0 +1,221 @@
+//! Using `mutatis` mutators as [`proptest`](https://docs.rs/proptest)
+//! strategies.
+//!
+//! The [`MutatisStrategy`] type in this module implements
+//! `proptest::strategy::Strategy` on top of a `mutatis` mutator and a seed
+//! corpus. New test cases are created by picking a value from the corpus and
+//! mutating it, and failing test cases are simplified with shrinking
+//! mutations. This lets you reuse your custom `mutatis` mutators inside an
+//! existing `proptest` suite, while keeping `proptest`'s runner, failure
+//! persistence, and reporting.
+//!
+//! # Example
+//!
+//! ```
+//! use mutatis::{mutators as m, proptest::MutatisStrategy};
+//! use proptest::{prelude::*, test_runner::TestRunner};
+//!
+//! // A strategy for pairs of `u32`s, created by mutating a seed corpus.
+//! let strategy = MutatisStrategy::new(m::tuple2(m::u32(), m::u32()), [(0, 0), (1, 2)]);
+//!
+//! // Use the strategy with `proptest`'s test runner, just like any other
+//! // strategy. It works inside the `proptest!` macro as well.
+//! let mut runner = TestRunner::default();
+//! runner
+//!     .run(&strategy, |(a, b)| {
+//!         prop_assert_eq!(a.wrapping_add(b), b.wrapping_add(a));
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use super::*;
+use ::proptest::{
+    prelude::Rng,
+    strategy::{NewTree, Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+use core::fmt::{self, Debug};
+use std::{format, vec::Vec};
+
+/// A `proptest` strategy that creates values by mutating a seed corpus with a
+/// `mutatis` mutator.
+///
+/// Each new test case is created by choosing a random value from the seed
+/// corpus and applying a random number of mutations to it, up to the
+/// configured [`max_mutations`][MutatisStrategy::max_mutations]. When a test
+/// case fails, `proptest` simplifies it by applying shrinking mutations via a
+/// shrinking [`Session`].
+///
+/// The `mutatis` session for each test case is seeded from `proptest`'s test
+/// runner, so test cases are reproducible with `proptest`'s usual
+/// configuration.
+///
+/// See [the module-level documentation][crate::proptest] for example usage.
+pub struct MutatisStrategy<M, T> {
+    mutator: M,
+    corpus: Vec<T>,
+    max_mutations: usize,
+    shrink_iters: usize,
+}
+
+impl<M, T> Debug for MutatisStrategy<M, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MutatisStrategy")
+            .field("corpus", &self.corpus)
+            .field("max_mutations", &self.max_mutations)
+            .field("shrink_iters", &self.shrink_iters)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<M, T> MutatisStrategy<M, T> {
+    /// Create a new strategy from the given mutator and seed corpus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the corpus is empty.
+    pub fn new(mutator: M, corpus: impl IntoIterator<Item = T>) -> Self {
+        let corpus = corpus.into_iter().collect::<Vec<_>>();
+        assert!(
+            !corpus.is_empty(),
+            "`MutatisStrategy` requires a non-empty seed corpus"
+        );
+        MutatisStrategy {
+            mutator,
+            corpus,
+            max_mutations: 10,
+            shrink_iters: 1000,
+        }
+    }
+
+    /// Configure the maximum number of mutations to apply to a seed corpus
+    /// value when creating a new test case.
+    ///
+    /// Defaults to `10`.
+    pub fn max_mutations(mut self, max_mutations: usize) -> Self {
+        self.max_mutations = max_mutations;
+        self
+    }
+
+    /// Configure the maximum number of shrinking mutations to attempt when
+    /// simplifying a failing test case.
+    ///
+    /// Note that `proptest`'s own `max_shrink_iters` configuration also
+    /// applies.
+    ///
+    /// Defaults to `1000`.
+    pub fn shrink_iters(mut self, shrink_iters: usize) -> Self {
+        self.shrink_iters = shrink_iters;
+        self
+    }
+}
+
+impl<M, T> Strategy for MutatisStrategy<M, T>
+where
+    M: Mutate<T> + Clone,
+    T: Clone + Debug,
+{
+    type Tree = MutatisValueTree<M, T>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let mut session = Session::new().seed(runner.rng().next_u64());
+        let mut mutator = self.mutator.clone();
+
+        let mut value = session.context.rng().choose(&self.corpus).unwrap().clone();
+
+        let mutations = session
+            .context
+            .rng()
+            .gen_index(self.max_mutations + 1)
+            .unwrap();
+        for _ in 0..mutations {
+            match session.mutate_with(&mut mutator, &mut value) {
+                Ok(()) => {}
+                Err(e) if e.is_exhausted() => break,
+                Err(e) => return Err(format!("mutatis mutator failed: {e}").into()),
+            }
+        }
+
+        Ok(MutatisValueTree {
+            mutator,
+            session: session.shrink(true),
+            current: value,
+            previous: None,
+            shrink_iters: self.shrink_iters,
+        })
+    }
+}
+
+/// The `proptest` value tree for [`MutatisStrategy`].
+///
+/// Simplifying the value tree applies a shrinking mutation to the current
+/// value. Complicating it reverts the most recent simplification.
+pub struct MutatisValueTree<M, T> {
+    mutator: M,
+    session: Session,
+    current: T,
+    previous: Option<T>,
+    shrink_iters: usize,
+}
+
+impl<M, T> Debug for MutatisValueTree<M, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MutatisValueTree")
+            .field("current", &self.current)
+            .field("previous", &self.previous)
+            .field("shrink_iters", &self.shrink_iters)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<M, T> ValueTree for MutatisValueTree<M, T>
+where
+    M: Mutate<T>,
+    T: Clone + Debug,
+{
+    type Value = T;
+
+    fn current(&self) -> T {
+        self.current.clone()
+    }
+
+    fn simplify(&mut self) -> bool {
+        if self.shrink_iters == 0 {
+            return false;
+        }
+        self.shrink_iters -= 1;
+
+        let mut candidate = self.current.clone();
+        match self.session.mutate_with(&mut self.mutator, &mut candidate) {
+            Ok(()) => {
+                self.previous = Some(core::mem::replace(&mut self.current, candidate));
+                true
+            }
+            // Either the mutator is exhausted, and there is nothing left to
+            // simplify, or it failed, in which case we stop simplifying and
+            // let `proptest` report the failure we already have.
+            Err(_) => {
+                self.shrink_iters = 0;
+                false
+            }
+        }
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.previous.take() {
+            Some(previous) => {
+                self.current = previous;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Using `mutatis` mutators with [`quickcheck`](https://docs.rs/quickcheck).
+//!
+//! The [`Mutated<T>`][Mutated] wrapper type in this module implements
+//! `quickcheck::Arbitrary` for any `T` whose default `mutatis` mutator can
+//! generate values from scratch. New values are created with `T`'s
+//! [`Generate`] implementation, and failing values are shrunk with shrinking
+//! mutations. This lets existing `quickcheck` properties consume input types
+//! whose test-case generation is defined with `mutatis`, which is useful when
+//! gradually migrating from `quickcheck` to `mutatis`.
+//!
+//! # Example
+//!
+//! ```
+//! use mutatis::quickcheck::Mutated;
+//! use quickcheck::QuickCheck;
+//!
+//! fn prop_add_sub_roundtrip(x: Mutated<u32>, y: Mutated<u32>) -> bool {
+//!     x.wrapping_add(*y).wrapping_sub(*y) == *x
+//! }
+//!
+//! QuickCheck::new().quickcheck(prop_add_sub_roundtrip as fn(Mutated<u32>, Mutated<u32>) -> bool);
+//! ```
+
+use super::*;
+use ::quickcheck::{Arbitrary, Gen};
+use core::ops::{Deref, DerefMut};
+use std::boxed::Box;
+
+/// The maximum number of shrunken candidates that a single call to
+/// `Mutated::shrink` yields.
+const MAX_SHRINK_CANDIDATES: usize = 100;
+
+/// The seed for the shrinking session created by `Mutated::shrink`.
+///
+/// `quickcheck::Arbitrary::shrink` is not given a `Gen`, so we cannot derive a
+/// seed from it. Using a fixed seed makes the shrunken candidates a
+/// deterministic function of the value being shrunk, so that shrinking is
+/// reproducible whenever generation is.
+const SHRINK_SEED: u64 = 0x6d75_7461_7469_7321;
+
+/// A wrapper type that implements `quickcheck::Arbitrary` via `T`'s default
+/// `mutatis` mutator.
+///
+/// Values are generated with a [`Session`] that is seeded from the given
+/// `quickcheck::Gen`, and whose [maximum size][Session::max_size] is the
+/// `Gen`'s [size][Gen::size], interpreted as a number of bytes. Shrinking is
+/// deterministic: shrinking equal values always yields the same candidates,
+/// none of which are equal to the value being shrunk.
+///
+/// See [the module-level documentation][crate::quickcheck] for example usage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mutated<T>(pub T);
+
+impl<T> Mutated<T> {
+    /// Unwrap this `Mutated<T>`, returning the inner `T` value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Mutated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Mutated<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> Arbitrary for Mutated<T>
+where
+    T: Clone + PartialEq + DefaultMutate + 'static,
+    T::DefaultMutate: Generate<T>,
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        let seed = u64::arbitrary(g);
+        let mut session = Session::new().seed(seed).max_size(g.size());
+        let value = mutators::default::<T>()
+            .generate(&mut session.context)
+            .unwrap_or_else(|e| panic!("failed to generate a value with `mutatis`: {e}"));
+        Mutated(value)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let value = self.0.clone();
+        let mut mutator = mutators::default::<T>();
+        let mut session = Session::new().seed(SHRINK_SEED).shrink(true);
+        let mut remaining = MAX_SHRINK_CANDIDATES;
+
+        Box::new(core::iter::from_fn(move || {
+            while remaining > 0 {
+                remaining -= 1;
+
+                let mut candidate = value.clone();
+                match session.mutate_with(&mut mutator, &mut candidate) {
+                    // Never yield the value itself: `quickcheck` would accept
+                    // it as a failing shrunken input and, because shrinking is
+                    // deterministic, shrink it the same way again forever.
+                    Ok(()) if candidate == value => continue,
+                    Ok(()) => return Some(Mutated(candidate)),
+                    // The mutator is exhausted (there are no smaller values) or
+                    // failed; either way, there is nothing more to yield.
+                    Err(_) => break,
+                }
+            }
+            remaining = 0;
+            None
+        }))
+    }
+}
@@ -0,0 +1,449 @@
+//! Standalone test-case reduction.
+//!
+//! The entry point for this module is the [`Reducer`] type.
+//!
+//! When a fuzzer finds an input that crashes your program, that input is often
+//! large and full of noise that is irrelevant to the bug. A `Reducer` takes
+//! such an input, a mutator, and an "is this input still interesting?"
+//! predicate, and repeatedly applies shrinking mutations to the input,
+//! accepting each smaller input that is still interesting. The result is a
+//! minimized input that is much easier to understand and debug.
+//!
+//! This is the same shrinking logic that [`mutatis::check`][mod@crate::check]
+//! uses to minimize failing inputs, but usable outside of property-based tests.
+//!
+//! # Example
+//!
+//! ```
+//! use mutatis::{mutators as m, reduce::Reducer};
+//! use std::time::Duration;
+//!
+//! /// Our program under test, which crashes on large inputs.
+//! fn crashes(x: &u32) -> bool {
+//!     *x >= 1000
+//! }
+//!
+//! // An input that our fuzzer found.
+//! let crash_input = 0xdead_beef;
+//!
+//! let reduced = Reducer::new()
+//!     .time_limit(Duration::from_secs(10))
+//!     .on_progress(|progress| {
+//!         println!("made {} reductions so far", progress.reductions);
+//!     })
+//!     .reduce(m::u32(), crash_input, crashes);
+//!
+//! // The reduced input still crashes our program, but is smaller than the
+//! // original input.
+//! assert!(crashes(&reduced));
+//! assert!(reduced <= crash_input);
+//! println!("reduced {crash_input} down to {reduced}");
+//! ```
+
+use super::*;
+use crate::log;
+use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
+use std::boxed::Box;
+use std::collections::{hash_map::DefaultHasher, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// The number of consecutive random shrinking attempts that fail to make
+/// progress before we switch to trying every shrinking mutation in turn.
+const STALL_LIMIT: usize = 1000;
+
+/// The number of recently checked candidates whose hashes we remember, to
+/// avoid re-checking them.
+const RECENT_CANDIDATES: usize = 4096;
+
+/// Progress information about an ongoing reduction.
+///
+/// Passed to [`Reducer::on_progress`] callbacks.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct ReduceProgress {
+    /// The number of candidate inputs that have been checked against the
+    /// predicate so far.
+    pub iters: usize,
+
+    /// The number of times that a smaller, still-interesting input has been
+    /// accepted so far.
+    pub reductions: usize,
+
+    /// The number of mutator errors (other than exhaustion) that were
+    /// encountered and ignored so far.
+    pub mutator_errors: usize,
+
+    /// The amount of time spent reducing so far.
+    pub elapsed: Duration,
+}
+
+/// A test-case reducer.
+///
+/// Given a value, a mutator, and a predicate that determines whether a value is
+/// still "interesting" (for example, it still crashes your program), a
+/// `Reducer` minimizes the value by repeatedly applying shrinking mutations and
+/// keeping the results that are still interesting.
+///
+/// Reduction first applies random shrinking mutations and, once those stop
+/// making progress, sweeps over every candidate shrinking mutation of the
+/// current value in turn. Each reduction that the sweep finds is accepted
+/// immediately, and the sweep continues with the new value. Reduction stops
+/// when a full sweep over every candidate finds no smaller value that is
+/// still interesting, or when a configured iteration or time limit is
+/// reached, whichever comes first.
+///
+/// This sweep is best-effort: it tries each candidate that the mutator
+/// registers once per sweep, but many candidates make a random change when
+/// applied, such as choosing a random smaller integer. The final value is
+/// therefore not guaranteed to be minimal with respect to every possible
+/// shrink.
+///
+/// See [the module-level documentation][crate::reduce] for example usage.
+pub struct Reducer<'a> {
+    max_iters: Option<usize>,
+    time_limit: Option<Duration>,
+    seed: Option<u64>,
+    on_progress: Option<ProgressCallback<'a>>,
+}
+
+type ProgressCallback<'a> = Box<dyn FnMut(&ReduceProgress) + 'a>;
+
+impl Debug for Reducer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reducer")
+            .field("max_iters", &self.max_iters)
+            .field("time_limit", &self.time_limit)
+            .field("seed", &self.seed)
+            .field("on_progress", &self.on_progress.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+impl Default for Reducer<'_> {
+    fn default() -> Self {
+        Reducer::new()
+    }
+}
+
+impl<'a> Reducer<'a> {
+    /// Create a new `Reducer`.
+    ///
+    /// By default, there is no iteration nor time limit, and reduction
+    /// continues until no shrinking mutation yields a smaller interesting
+    /// value.
+    pub fn new() -> Self {
+        Reducer {
+            max_iters: None,
+            time_limit: None,
+            seed: None,
+            on_progress: None,
+        }
+    }
+
+    /// Configure the maximum number of candidate values to check against the
+    /// predicate before returning the smallest interesting value found so far.
+    pub fn max_iters(&mut self, max_iters: usize) -> &mut Self {
+        self.max_iters = Some(max_iters);
+        self
+    }
+
+    /// Configure the maximum amount of time to spend reducing before returning
+    /// the smallest interesting value found so far.
+    pub fn time_limit(&mut self, time_limit: Duration) -> &mut Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Configure the seed for the random number generator, making reduction
+    /// deterministic (provided that the predicate is deterministic as well).
+    ///
+    /// By default, every reduction within a process uses a different seed,
+    /// taken from a process-wide counter. Since that counter starts at the
+    /// same value in every process, the default seeds are the same across
+    /// processes.
+    pub fn seed(&mut self, seed: u64) -> &mut Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Register a callback that is invoked with the reduction's progress every
+    /// time a smaller, still-interesting value is accepted.
+    ///
+    /// This is useful for reporting progress during long-running reductions.
+    pub fn on_progress(&mut self, callback: impl FnMut(&ReduceProgress) + 'a) -> &mut Self {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Reduce `value` with the given `mutator`, returning the smallest value
+    /// found for which `is_interesting` returns `true`.
+    ///
+    /// The initial `value` is assumed to be interesting and is not checked
+    /// against the predicate. If no smaller interesting value is found, the
+    /// initial `value` is returned.
+    ///
+    /// An interesting candidate value that is equal to the current value is
+    /// never accepted, so that shrinking mutations which leave the value
+    /// unchanged cannot keep reduction from reaching a fixpoint. Candidate
+    /// values are only compared against the current value once the predicate
+    /// finds them interesting, and are otherwise checked against the
+    /// predicate even if an equal value was already checked. If your
+    /// predicate is expensive, or your mutator's shrinking mutations can
+    /// produce cycles of values, consider using
+    /// [`reduce_dedup`][Reducer::reduce_dedup] instead.
+    pub fn reduce<M, T>(
+        &mut self,
+        mutator: M,
+        value: T,
+        is_interesting: impl FnMut(&T) -> bool,
+    ) -> T
+    where
+        M: Mutate<T>,
+        T: Clone + PartialEq,
+    {
+        self.reduce_impl(mutator, value, |_| None, |a, b| a == b, is_interesting)
+            .0
+    }
+
+    /// Like [`reduce`][Reducer::reduce], but skips candidate values that are
+    /// equal to a recently checked value, rather than checking them against
+    /// the predicate again.
+    ///
+    /// Only the hashes of a bounded number of recent candidates are
+    /// remembered, so memory usage stays constant even during long
+    /// reductions of large values.
+    pub fn reduce_dedup<M, T>(
+        &mut self,
+        mutator: M,
+        value: T,
+        is_interesting: impl FnMut(&T) -> bool,
+    ) -> T
+    where
+        M: Mutate<T>,
+        T: Clone + Hash + Eq,
+    {
+        self.reduce_impl(
+            mutator,
+            value,
+            |c| Some(hash(c)),
+            |a, b| a == b,
+            is_interesting,
+        )
+        .0
+    }
+
+    /// Like `reduce` but additionally returns the final progress, so that
+    /// callers can report statistics about the reduction.
+    ///
+    /// If `key` returns `Some` hash for a candidate, then candidates whose
+    /// hash matches that of a recently checked candidate are skipped without
+    /// checking them against the predicate.
+    ///
+    /// Interesting candidates for which `same` returns `true`, when compared
+    /// against the current value, are not accepted.
+    pub(crate) fn reduce_impl<M, T>(
+        &mut self,
+        mut mutator: M,
+        mut value: T,
+        mut key: impl FnMut(&T) -> Option<u64>,
+        mut same: impl FnMut(&T, &T) -> bool,
+        mut is_interesting: impl FnMut(&T) -> bool,
+    ) -> (T, ReduceProgress)
+    where
+        M: Mutate<T>,
+        T: Clone,
+    {
+        let start = Instant::now();
+        let deadline = self.time_limit.map(|limit| start + limit);
+        let max_iters = self.max_iters.unwrap_or(usize::MAX);
+        let mut progress = ReduceProgress::default();
+
+        let mut session = match self.seed {
+            Some(seed) => Session::new().seed(seed),
+            None => Session::new(),
+        }
+        .shrink(true);
+
+        let mut recent = RecentHashes::default();
+        if let Some(k) = key(&value) {
+            recent.insert(k);
+        }
+
+        let out_of_budget = |progress: &ReduceProgress| {
+            progress.iters >= max_iters || deadline.is_some_and(|d| Instant::now() >= d)
+        };
+
+        let mut accept = |candidate: &T, current: &T, progress: &mut ReduceProgress| -> bool {
+            if key(candidate).is_some_and(|k| !recent.insert(k)) {
+                return false;
+            }
+            progress.iters += 1;
+            // Never accept a candidate that is equal to the current value:
+            // doing so would count as progress without actually making any,
+            // and keep us from ever reaching a fixpoint.
+            if is_interesting(candidate) && !same(candidate, current) {
+                log::debug!("made reduction {}", progress.reductions + 1);
+                progress.reductions += 1;
+                progress.elapsed = start.elapsed();
+                if let Some(on_progress) = self.on_progress.as_mut() {
+                    on_progress(progress);
+                }
+                true
+            } else {
+                false
+            }
+        };
+
+        'reducing: {
+            // First, apply random shrinking mutations until they stall.
+            let mut stalled = 0;
+            while stalled < STALL_LIMIT {
+                if out_of_budget(&progress) {
+                    break 'reducing;
+                }
+
+                let mut candidate = value.clone();
+                match session.mutate_with(&mut mutator, &mut candidate) {
+                    // If the mutator is exhausted, then there is nothing left
+                    // to try.
+                    Err(e) if e.is_exhausted() => break 'reducing,
+
+                    // Ignore mutator errors during reduction because the
+                    // interesting value we already have is more important.
+                    Err(e) => {
+                        log::info!("got mutator error during reduction, ignoring: {e}");
+                        progress.mutator_errors += 1;
+                        stalled += 1;
+                        continue;
+                    }
+
+                    Ok(()) => {}
+                }
+
+                if accept(&candidate, &value, &mut progress) {
+                    value = candidate;
+                    stalled = 0;
+                } else {
+                    stalled += 1;
+                }
+            }
+
+            // Then, once random shrinking stops making progress, sweep over
+            // every shrinking mutation of the current value in turn, greedily
+            // accepting each one that is still interesting. After accepting a
+            // reduction, the sweep resumes at the same candidate of the new
+            // value, wrapping around at the end, until a full sweep over every
+            // candidate makes no progress.
+            log::debug!("random reduction stalled, enumerating all shrinks");
+            let Some(mut count) = count_shrinks(&mut session, &mut mutator, &value, &mut progress)
+            else {
+                break 'reducing;
+            };
+
+            let mut target = 0;
+            let mut untried = count;
+            while untried > 0 {
+                if out_of_budget(&progress) {
+                    break 'reducing;
+                }
+                untried -= 1;
+
+                let mut candidate = value.clone();
+                match session.context.apply_candidate_with(
+                    &mut mutator,
+                    &mut candidate,
+                    target,
+                    count,
+                ) {
+                    Ok(()) if accept(&candidate, &value, &mut progress) => {
+                        value = candidate;
+                        let Some(new_count) =
+                            count_shrinks(&mut session, &mut mutator, &value, &mut progress)
+                        else {
+                            break 'reducing;
+                        };
+                        count = new_count;
+                        untried = count;
+                        // Stay on the same candidate, since it may well shrink
+                        // the new value even further.
+                        if target < count {
+                            continue;
+                        }
+                    }
+                    Ok(()) => {}
+                    Err(e) => {
+                        if !e.is_exhausted() {
+                            log::info!("got mutator error during reduction, ignoring: {e}");
+                            progress.mutator_errors += 1;
+                        }
+                    }
+                }
+
+                target = if target + 1 < count { target + 1 } else { 0 };
+            }
+
+            // No shrinking mutation of the current value is still interesting,
+            // so we have reached a fixpoint.
+            log::debug!("reached reduction fixpoint");
+        }
+
+        progress.elapsed = start.elapsed();
+        log::info!("made {} reductions", progress.reductions);
+        (value, progress)
+    }
+}
+
+/// Count the shrinking mutations of `value`, or return `None` if the mutator
+/// fails.
+fn count_shrinks<T>(
+    session: &mut Session,
+    mutator: &mut impl Mutate<T>,
+    value: &T,
+    progress: &mut ReduceProgress,
+) -> Option<usize>
+where
+    T: Clone,
+{
+    match session
+        .context
+        .count_candidates_with(mutator, &mut value.clone())
+    {
+        Ok(count) => Some(count),
+        Err(e) => {
+            log::info!("got mutator error during reduction, ignoring: {e}");
+            progress.mutator_errors += 1;
+            None
+        }
+    }
+}
+
+/// A bounded set of the hashes of recently checked candidates.
+#[derive(Default)]
+struct RecentHashes {
+    set: HashSet<u64>,
+    order: VecDeque<u64>,
+}
+
+impl RecentHashes {
+    /// Insert a hash, evicting the oldest hash if we are at capacity.
+    ///
+    /// Returns `true` if the hash was not already present.
+    fn insert(&mut self, hash: u64) -> bool {
+        if !self.set.insert(hash) {
+            return false;
+        }
+        self.order.push_back(hash);
+        if self.order.len() > RECENT_CANDIDATES {
+            let oldest = self.order.pop_front().unwrap();
+            self.set.remove(&oldest);
+        }
+        true
+    }
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
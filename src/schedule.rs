@@ -0,0 +1,100 @@
+//! Adaptive scheduling of named candidate mutations.
+//!
+//! When enabled with [`Session::adaptive`][crate::Session::adaptive], the
+//! session tracks which named candidate mutations were applied to each input,
+//! and whether the caller reported that input as interesting via
+//! [`Session::feedback`][crate::Session::feedback]. Candidates whose names have
+//! historically led to interesting inputs more often than average are then
+//! chosen more often, and those that have led to them less often are chosen
+//! less often, similar to the MOpt mutation scheduler.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Stats {
+    uses: u32,
+    successes: u32,
+}
+
+impl Stats {
+    fn record(&mut self, interesting: bool) {
+        self.uses += 1;
+        self.successes += u32::from(interesting);
+    }
+
+    fn decay(&mut self) {
+        self.uses /= 2;
+        self.successes /= 2;
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Scheduler {
+    /// Statistics for each candidate name.
+    stats: BTreeMap<&'static str, Stats>,
+
+    /// Statistics across all candidate names.
+    total: Stats,
+
+    /// The names of the candidates that were applied during the most recent
+    /// top-level mutation.
+    applied: Vec<&'static str>,
+}
+
+impl Scheduler {
+    /// The weight of unnamed candidates and of names without any feedback yet.
+    const BASE_WEIGHT: u32 = 16;
+
+    /// The maximum weight of any candidate.
+    const MAX_WEIGHT: u32 = Self::BASE_WEIGHT * 8;
+
+    /// How many uses of a name it takes for its success rate to dominate the
+    /// average success rate across all names.
+    const PRIOR_USES: f64 = 16.0;
+
+    /// Halve all statistics once this many mutations have received feedback,
+    /// so that weights keep adapting as the fuzzer's needs change over time.
+    const DECAY_PERIOD: u32 = 4096;
+
+    /// Get the weight of a candidate with the given name.
+    pub(crate) fn weight(&self, name: Option<&'static str>) -> u32 {
+        let Some(stats) = name.and_then(|n| self.stats.get(n)) else {
+            return Self::BASE_WEIGHT;
+        };
+
+        // Smooth this name's success rate towards the average success rate, so
+        // that names with few uses are not drastically over- or under-weighted.
+        let average = (f64::from(self.total.successes) + 1.0) / (f64::from(self.total.uses) + 2.0);
+        let rate = (f64::from(stats.successes) + Self::PRIOR_USES * average)
+            / (f64::from(stats.uses) + Self::PRIOR_USES);
+
+        let weight = f64::from(Self::BASE_WEIGHT) * rate / average;
+        (weight + 0.5).clamp(1.0, f64::from(Self::MAX_WEIGHT)) as u32
+    }
+
+    /// Start a new top-level mutation.
+    pub(crate) fn begin(&mut self) {
+        self.applied.clear();
+    }
+
+    /// Record that a candidate with the given name was applied.
+    pub(crate) fn applied(&mut self, name: &'static str) {
+        self.applied.push(name);
+    }
+
+    /// Record whether the most recent top-level mutation produced an
+    /// interesting input.
+    pub(crate) fn feedback(&mut self, interesting: bool) {
+        for name in self.applied.drain(..) {
+            self.stats.entry(name).or_default().record(interesting);
+            self.total.record(interesting);
+        }
+
+        if self.total.uses >= Self::DECAY_PERIOD {
+            self.total.decay();
+            for stats in self.stats.values_mut() {
+                stats.decay();
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Estimating the size of values, for use with a [`Session`][crate::Session]'s
+//! [maximum size][crate::Session::max_size].
+
+/// A trait for estimating the size of a value, in bytes.
+///
+/// This is used to bound the growth of values during mutation: see
+/// [`Session::max_size`][crate::Session::max_size] and
+/// [`Session::mutate_sized_with`][crate::Session::mutate_sized_with].
+///
+/// The size hint is an estimate of how many bytes of data a value contains, for
+/// example its size once serialized into a fuzzer's test case. It need not be
+/// exact, but it should grow as the value grows. Implementations for
+/// collections should sum the size hints of their elements.
+///
+/// # Example
+///
+/// ```
+/// use mutatis::SizeHint;
+///
+/// struct Packet {
+///     kind: u8,
+///     payload: [u32; 4],
+/// }
+///
+/// impl SizeHint for Packet {
+///     fn size_hint(&self) -> usize {
+///         self.kind.size_hint() + self.payload.size_hint()
+///     }
+/// }
+///
+/// let packet = Packet { kind: 1, payload: [0; 4] };
+/// assert_eq!(packet.size_hint(), 17);
+/// ```
+pub trait SizeHint {
+    /// Get the estimated size of this value, in bytes.
+    fn size_hint(&self) -> usize;
+}
+
+macro_rules! primitives {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl SizeHint for $ty {
+                #[inline]
+                fn size_hint(&self) -> usize {
+                    core::mem::size_of::<$ty>()
+                }
+            }
+        )*
+    };
+}
+
+primitives!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+);
+
+impl<T> SizeHint for &T
+where
+    T: ?Sized + SizeHint,
+{
+    #[inline]
+    fn size_hint(&self) -> usize {
+        (**self).size_hint()
+    }
+}
+
+impl<T> SizeHint for [T]
+where
+    T: SizeHint,
+{
+    fn size_hint(&self) -> usize {
+        self.iter().map(|x| x.size_hint()).sum()
+    }
+}
+
+impl<T, const N: usize> SizeHint for [T; N]
+where
+    T: SizeHint,
+{
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self[..].size_hint()
+    }
+}
+
+impl SizeHint for str {
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> SizeHint for Option<T>
+where
+    T: SizeHint,
+{
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.as_ref().map_or(0, |x| x.size_hint())
+    }
+}
+
+impl<T, E> SizeHint for Result<T, E>
+where
+    T: SizeHint,
+    E: SizeHint,
+{
+    #[inline]
+    fn size_hint(&self) -> usize {
+        match self {
+            Ok(x) => x.size_hint(),
+            Err(e) => e.size_hint(),
+        }
+    }
+}
+
+macro_rules! tuples {
+    ( $( ( $( $t:ident , )* ) ; )* ) => {
+        $(
+            #[allow(non_snake_case)]
+            impl< $( $t , )* > SizeHint for ( $( $t , )* )
+            where
+                $(
+                    $t: SizeHint,
+                )*
+            {
+                #[inline]
+                fn size_hint(&self) -> usize {
+                    let ( $( $t , )* ) = self;
+                    0 $( + $t.size_hint() )*
+                }
+            }
+        )*
+    };
+}
+
+tuples! {
+    (T0,);
+    (T0, T1,);
+    (T0, T1, T2,);
+    (T0, T1, T2, T3,);
+    (T0, T1, T2, T3, T4,);
+    (T0, T1, T2, T3, T4, T5,);
+    (T0, T1, T2, T3, T4, T5, T6,);
+    (T0, T1, T2, T3, T4, T5, T6, T7,);
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8,);
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9,);
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10,);
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11,);
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12,);
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13,);
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14,);
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15,);
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SizeHint for alloc::boxed::Box<T>
+where
+    T: ?Sized + SizeHint,
+{
+    #[inline]
+    fn size_hint(&self) -> usize {
+        (**self).size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SizeHint for alloc::vec::Vec<T>
+where
+    T: SizeHint,
+{
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self[..].size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl SizeHint for alloc::string::String {
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
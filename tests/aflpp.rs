@@ -0,0 +1,121 @@
+#![cfg(all(feature = "aflpp", feature = "derive"))]
+
+use mutatis::{aflpp::CustomMutator, codec::Codec, Mutate};
+use std::ptr;
+
+#[derive(Clone, Debug, PartialEq, Eq, Mutate)]
+struct Pair(u8, u8);
+
+struct PairCodec;
+
+impl Codec<Pair> for PairCodec {
+    fn decode(bytes: &[u8]) -> Option<Pair> {
+        let [a, b] = *bytes.first_chunk::<2>()?;
+        Some(Pair(a, b))
+    }
+
+    fn encode(value: &Pair, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&[value.0, value.1]);
+    }
+}
+
+mutatis::aflpp_custom_mutator!(Pair, PairCodec);
+
+struct PanickingCodec;
+
+impl Codec<Pair> for PanickingCodec {
+    fn decode(bytes: &[u8]) -> Option<Pair> {
+        PairCodec::decode(bytes)
+    }
+
+    fn encode(_value: &Pair, _bytes: &mut Vec<u8>) {
+        panic!("failed to encode");
+    }
+}
+
+#[test]
+fn custom_mutator_mutates() {
+    let mut mutator = CustomMutator::<Pair, PairCodec>::new(1);
+    let out = mutator.fuzz(&[1, 2], 16);
+    assert_eq!(out.len(), 2);
+    assert_ne!(out, [1, 2]);
+}
+
+#[test]
+fn custom_mutator_is_deterministic() {
+    let run = |seed| {
+        let mut mutator = CustomMutator::<Pair, PairCodec>::new(seed);
+        (0..10)
+            .map(|_| mutator.fuzz(&[1, 2], 16).to_vec())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(run(42), run(42));
+}
+
+#[test]
+fn custom_mutator_skips_undecodable_input() {
+    let mut mutator = CustomMutator::<Pair, PairCodec>::new(1);
+    assert!(mutator.fuzz(&[1], 16).is_empty());
+}
+
+#[test]
+fn custom_mutator_respects_max_size() {
+    let mut mutator = CustomMutator::<Pair, PairCodec>::new(1);
+    assert!(mutator.fuzz(&[1, 2], 1).is_empty());
+}
+
+#[test]
+fn custom_mutator_uses_session_cmp_log() {
+    let mut mutator = CustomMutator::<Pair, PairCodec>::new(1);
+    mutator.session().cmp_log().observe_u8(1, 0xaa);
+    let found = (0..100).any(|_| mutator.fuzz(&[1, 2], 16).contains(&0xaa));
+    assert!(found);
+}
+
+#[test]
+fn exported_functions() {
+    unsafe {
+        let data = afl_custom_init(ptr::null_mut(), 1);
+        assert!(!data.is_null());
+
+        let mut buf = [1, 2];
+        let mut out_buf = ptr::null_mut();
+        let size = afl_custom_fuzz(
+            data,
+            buf.as_mut_ptr(),
+            buf.len(),
+            &mut out_buf,
+            ptr::null_mut(),
+            0,
+            16,
+        );
+        assert_eq!(size, 2);
+        let out = std::slice::from_raw_parts(out_buf, size);
+        assert_ne!(out, [1, 2]);
+
+        afl_custom_deinit(data);
+    }
+}
+
+#[test]
+fn exported_fuzz_skips_mutation_on_panic() {
+    unsafe {
+        let data = mutatis::aflpp::init::<Pair, PanickingCodec>(ptr::null_mut(), 1);
+
+        let mut buf = [1, 2];
+        let mut out_buf = ptr::null_mut();
+        let size = mutatis::aflpp::fuzz::<Pair, PanickingCodec>(
+            data,
+            buf.as_mut_ptr(),
+            buf.len(),
+            &mut out_buf,
+            ptr::null_mut(),
+            0,
+            16,
+        );
+        assert_eq!(size, 0);
+        assert!(!out_buf.is_null());
+
+        mutatis::aflpp::deinit::<Pair, PanickingCodec>(data);
+    }
+}
@@ -0,0 +1,110 @@
+#![cfg(all(feature = "arbitrary", feature = "std"))]
+
+use arbitrary::Arbitrary;
+use mutatis::{mutators as m, Session};
+
+#[derive(Arbitrary, Clone, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn from_arbitrary_mutate() -> mutatis::Result<()> {
+    let mut session = Session::new().seed(1);
+    let mut mutator = m::from_arbitrary::<Point>();
+
+    let original = Point { x: 0, y: 0 };
+    let mut value = original.clone();
+    let mut changed = false;
+    for _ in 0..10 {
+        session.mutate_with(&mut mutator, &mut value)?;
+        changed |= value != original;
+    }
+    assert!(changed);
+    Ok(())
+}
+
+#[test]
+fn from_arbitrary_collections() -> mutatis::Result<()> {
+    let mut session = Session::new().seed(2);
+    let mut mutator = m::from_arbitrary::<Vec<String>>();
+
+    let mut value = vec![];
+    let mut saw_non_empty = false;
+    for _ in 0..100 {
+        session.mutate_with(&mut mutator, &mut value)?;
+        saw_non_empty |= !value.is_empty();
+    }
+    assert!(saw_non_empty);
+    Ok(())
+}
+
+#[test]
+fn from_arbitrary_as_option_leaf() -> mutatis::Result<()> {
+    let mut session = Session::new().seed(3);
+    let mut mutator = m::option(m::from_arbitrary::<Point>());
+
+    let mut value = None;
+    let mut saw_some = false;
+    for _ in 0..20 {
+        session.mutate_with(&mut mutator, &mut value)?;
+        saw_some |= value.is_some();
+    }
+    assert!(saw_some);
+    Ok(())
+}
+
+#[test]
+fn from_arbitrary_does_not_shrink() {
+    let mut session = Session::new().shrink(true);
+    let mut value = Point { x: 1, y: 2 };
+    let err = session
+        .mutate_with(&mut m::from_arbitrary(), &mut value)
+        .unwrap_err();
+    assert!(err.is_exhausted());
+}
+
+#[test]
+fn from_arbitrary_huge_size_hint() -> mutatis::Result<()> {
+    #[derive(Clone, Debug)]
+    struct Huge(Vec<u8>);
+
+    impl<'a> Arbitrary<'a> for Huge {
+        fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            Ok(Huge(u.arbitrary()?))
+        }
+
+        fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+            (0, Some(usize::MAX))
+        }
+    }
+
+    let mut session = Session::new().seed(3);
+    let mut mutator = m::from_arbitrary::<Huge>();
+    let mut value = Huge(vec![]);
+    for _ in 0..10 {
+        session.mutate_with(&mut mutator, &mut value)?;
+        assert!(value.0.len() <= 4096);
+    }
+    Ok(())
+}
+
+#[test]
+fn from_arbitrary_rejected_data_is_exhausted() {
+    #[derive(Clone, Debug)]
+    struct Rejects;
+
+    impl<'a> Arbitrary<'a> for Rejects {
+        fn arbitrary(_u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            Err(arbitrary::Error::IncorrectFormat)
+        }
+    }
+
+    let mut session = Session::new().seed(4);
+    let mut value = Rejects;
+    let err = session
+        .mutate_with(&mut m::from_arbitrary(), &mut value)
+        .unwrap_err();
+    assert!(err.is_exhausted());
+}
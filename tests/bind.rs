@@ -0,0 +1,33 @@
+use mutatis::{mutators as m, Mutate, Session};
+
+#[test]
+fn bind_depends_on_value() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::bind(|(lo, _): &(u8, u8)| m::range(*lo..=u8::MAX).proj(|(_, hi)| hi));
+    for lo in [0, 10, 200, u8::MAX] {
+        let mut value = (lo, u8::MAX);
+        for _ in 0..100 {
+            session.mutate_with(&mut mutator, &mut value).unwrap();
+            assert_eq!(value.0, lo);
+            assert!(value.1 >= lo);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn bind_chooses_mutator_per_value() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::bind(|x: &u32| {
+        if *x % 2 == 0 {
+            m::just(1).boxed()
+        } else {
+            m::just(2).boxed()
+        }
+    });
+    let mut value = 0;
+    session.mutate_with(&mut mutator, &mut value).unwrap();
+    assert_eq!(value, 1);
+    session.mutate_with(&mut mutator, &mut value).unwrap();
+    assert_eq!(value, 2);
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "std")]
+
+use mutatis::{mutators as m, DynMutate, Mutate, Session};
+
+#[test]
+fn mutate_through_boxed_mutators() {
+    #[derive(Debug)]
+    struct Config {
+        mutators: Vec<Box<dyn DynMutate<u64>>>,
+    }
+
+    let config = Config {
+        mutators: vec![m::range(0..=10).boxed(), m::just(42).boxed()],
+    };
+    assert!(format!("{config:?}").contains("DynMutate"));
+
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::one_of(config.mutators);
+    let mut value = 5;
+    for _ in 0..100 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        assert!(value <= 10 || value == 42);
+    }
+}
+
+#[test]
+fn mutate_through_dyn_mutate_references() {
+    fn mutate_dyn(mut mutator: &mut dyn DynMutate<u8>, value: &mut u8) {
+        let mut session = Session::new().seed(0x1234);
+        session.mutate_with(&mut mutator, value).unwrap();
+    }
+
+    let mut value = 0;
+    mutate_dyn(&mut m::range(10..=20), &mut value);
+    assert!((10..=20).contains(&value));
+}
+
+#[test]
+fn mutate_through_boxed_send_mutators() {
+    let mut mutator: Box<dyn DynMutate<u32> + Send> = Box::new(m::range(0..=100));
+    assert!(format!("{mutator:?}").contains("DynMutate"));
+
+    let value = std::thread::spawn(move || {
+        let mut session = Session::new().seed(0x1234);
+        let mut value = 1000;
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        value
+    })
+    .join()
+    .unwrap();
+    assert!(value <= 100);
+}
+
+#[test]
+fn mutate_through_boxed_plain_dyn_mutators() {
+    let mut mutator: Box<dyn Mutate<u32>> = Box::new(m::range(0..=100));
+    assert!(format!("{mutator:?}").contains("dyn Mutate"));
+
+    let mut session = Session::new().seed(0x1234);
+    let mut value = 1000;
+    session.mutate_with(&mut mutator, &mut value).unwrap();
+    assert!(value <= 100);
+}
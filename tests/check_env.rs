@@ -0,0 +1,112 @@
+#![cfg(feature = "check")]
+
+use mutatis::{check::Check, mutators as m};
+use std::sync::Mutex;
+
+/// Environment variables are process-wide, so tests that set them must not run
+/// concurrently.
+static ENV: Mutex<()> = Mutex::new(());
+
+const VARS: [&str; 4] = [
+    "MUTATIS_ITERS",
+    "MUTATIS_SHRINK_ITERS",
+    "MUTATIS_SEED",
+    "MUTATIS_TIME_BUDGET",
+];
+
+/// Run `f` with only the given `MUTATIS_*` environment variables set, removing
+/// them again afterwards, even if `f` panics.
+fn with_env<R>(vars: &[(&str, &str)], f: impl FnOnce() -> R) -> R {
+    fn clear() {
+        for name in VARS {
+            std::env::remove_var(name);
+        }
+    }
+
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            clear();
+        }
+    }
+
+    let _lock = ENV.lock().unwrap_or_else(|e| e.into_inner());
+    clear();
+    let _reset = Reset;
+    for (name, value) in vars {
+        std::env::set_var(name, value);
+    }
+    f()
+}
+
+#[test]
+fn env_iters_overrides_builder() {
+    with_env(&[("MUTATIS_ITERS", "10")], || {
+        let (result, stats) = Check::new()
+            .iters(1_000)
+            .run_with_stats(m::u32(), [0], |_: &u32| Ok::<_, String>(()));
+        result.unwrap();
+        assert_eq!(stats.inputs_checked, 11);
+    });
+}
+
+#[test]
+fn env_shrink_iters_overrides_builder() {
+    with_env(&[("MUTATIS_SHRINK_ITERS", "0")], || {
+        let failure = Check::new()
+            .shrink_iters(1_000)
+            .run_with(m::u32(), [u32::MAX], |x: &u32| {
+                if *x >= 1_000 {
+                    Err("too large")
+                } else {
+                    Ok(())
+                }
+            })
+            .unwrap_err()
+            .unwrap_failed();
+        assert_eq!(failure.value, u32::MAX);
+    });
+}
+
+#[test]
+fn env_seed_overrides_builder() {
+    let inputs = |builder_seed| {
+        let mut inputs = vec![];
+        Check::new()
+            .iters(100)
+            .seed(builder_seed)
+            .run_with(m::u32(), [0], |x: &u32| {
+                inputs.push(*x);
+                Ok::<_, String>(())
+            })
+            .unwrap();
+        inputs
+    };
+
+    with_env(&[("MUTATIS_SEED", "42")], || {
+        assert_eq!(inputs(1), inputs(2));
+    });
+    with_env(&[], || {
+        assert_ne!(inputs(1), inputs(2));
+    });
+}
+
+#[test]
+fn env_time_budget_overrides_builder() {
+    with_env(&[("MUTATIS_TIME_BUDGET", "0")], || {
+        let (result, stats) = Check::new()
+            .time_budget(std::time::Duration::from_secs(3600))
+            .run_with_stats(m::u32(), [0], |_: &u32| Ok::<_, String>(()));
+        result.unwrap();
+        // Only the initial corpus is checked.
+        assert_eq!(stats.inputs_checked, 1);
+    });
+}
+
+#[test]
+#[should_panic(expected = "invalid value for `MUTATIS_ITERS` environment variable")]
+fn env_invalid_value_panics() {
+    with_env(&[("MUTATIS_ITERS", "lots")], || {
+        let _ = Check::new().run_with(m::u32(), [0], |_: &u32| Ok::<_, String>(()));
+    });
+}
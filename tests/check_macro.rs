@@ -0,0 +1,46 @@
+#![cfg(all(feature = "check", feature = "derive"))]
+
+use mutatis::Mutate;
+
+#[mutatis::check]
+fn single_argument(x: &u8) -> Result<(), String> {
+    let _ = x.wrapping_add(1);
+    Ok(())
+}
+
+#[mutatis::check(iters = 100, shrink_iters = 10, corpus = [(1, true), (2, false)])]
+fn multiple_arguments(x: &u32, b: &bool) -> Result<(), &'static str> {
+    let sum = u64::from(*x) + u64::from(*b);
+    if sum >= u64::from(*x) {
+        Ok(())
+    } else {
+        Err("addition overflowed")
+    }
+}
+
+#[mutatis::check(corpus = [0])]
+fn by_value_argument_and_unit_return(x: i64) {
+    let _ = x.checked_mul(2);
+}
+
+#[derive(Clone, Debug, Default, Mutate)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[mutatis::check(corpus = [Point { x: 1, y: 2 }])]
+fn derived_argument(p: &Point) -> Result<(), String> {
+    let _ = (p.x, p.y);
+    Ok(())
+}
+
+#[mutatis::check(corpus = [u8::MAX])]
+#[should_panic(expected = "property `failing_property` failed on input 10")]
+fn failing_property(x: &u8) -> Result<(), String> {
+    if *x < 10 {
+        Ok(())
+    } else {
+        Err(format!("{x} is too big"))
+    }
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "alloc")]
+
+use mutatis::{cmplog::CmpLog, mutators as m, Session};
+
+#[test]
+fn cmp_operands_are_spliced_into_ints() {
+    let mut session = Session::new().seed(0x1234);
+    session.cmp_log().observe_u16(0x1111, 0xbeef);
+    let mut value = 0x1111u16;
+    let mut found = false;
+    for _ in 0..100 {
+        session.mutate_with(&mut m::u16(), &mut value).unwrap();
+        if value == 0xbeef {
+            found = true;
+            break;
+        }
+        value = 0x1111;
+    }
+    assert!(found);
+}
+
+#[test]
+fn cmp_operands_are_spliced_into_signed_ints() {
+    let mut session = Session::new().seed(0x1234);
+    session.cmp_log().observe_u32(-1i32 as u32, -42i32 as u32);
+    let mut value = -1i32;
+    let mut found = false;
+    for _ in 0..100 {
+        session.mutate_with(&mut m::i32(), &mut value).unwrap();
+        if value == -42 {
+            found = true;
+            break;
+        }
+        value = -1;
+    }
+    assert!(found);
+}
+
+#[test]
+fn cmp_byte_operands_are_spliced_into_ints() {
+    let mut session = Session::new().seed(0x1234);
+    session.cmp_log().observe_bytes(b"\0\0\0\0", b"\x7fELF");
+    session.cmp_log().observe_bytes(b"GET", b"PUT");
+    let mut seen_le = false;
+    let mut seen_be = false;
+    for _ in 0..1000 {
+        let mut value = 0u32;
+        session.mutate_with(&mut m::u32(), &mut value).unwrap();
+        seen_le |= value == u32::from_le_bytes(*b"\x7fELF");
+        seen_be |= value == u32::from_be_bytes(*b"\x7fELF");
+    }
+    assert!(seen_le && seen_be);
+}
+
+#[test]
+fn cmp_log_is_bounded() {
+    let mut cmp_log = CmpLog::with_capacity(2);
+    for i in 0..10 {
+        cmp_log.observe_u8(i, 0xff);
+        cmp_log.observe_bytes(b"GET", &[i]);
+    }
+    cmp_log.observe_u8(1, 1);
+    cmp_log.observe_bytes(b"GET", b"GET");
+    assert_eq!(cmp_log.len(), 4);
+    assert_eq!(
+        cmp_log.bytes().collect::<Vec<_>>(),
+        [(&b"GET"[..], &[8][..]), (b"GET", &[9])]
+    );
+
+    cmp_log.clear();
+    assert!(cmp_log.is_empty());
+}
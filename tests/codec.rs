@@ -0,0 +1,70 @@
+#![cfg(feature = "alloc")]
+
+use mutatis::codec::{Codec, RawBytes};
+
+fn roundtrip<T, C>(value: T)
+where
+    T: std::fmt::Debug + PartialEq,
+    C: Codec<T>,
+{
+    let mut bytes = vec![0xff];
+    C::encode(&value, &mut bytes);
+    assert_eq!(bytes[0], 0xff, "encoding should append to the given bytes");
+    assert_eq!(C::decode(&bytes[1..]), Some(value));
+}
+
+#[test]
+fn raw_bytes_roundtrip() {
+    roundtrip::<_, RawBytes>(vec![]);
+    roundtrip::<_, RawBytes>(b"hello".to_vec());
+}
+
+#[cfg(any(feature = "bincode", feature = "postcard"))]
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Structured {
+    x: u32,
+    name: String,
+    items: Vec<Option<i8>>,
+}
+
+#[cfg(any(feature = "bincode", feature = "postcard"))]
+fn structured() -> Structured {
+    Structured {
+        x: 42,
+        name: "hello".into(),
+        items: vec![Some(-1), None, Some(3)],
+    }
+}
+
+#[test]
+#[cfg(feature = "bincode")]
+fn bincode_roundtrip() {
+    use mutatis::codec::Bincode;
+    roundtrip::<_, Bincode>(structured());
+    roundtrip::<_, Bincode>((1_u8, true, 'x'));
+}
+
+#[test]
+#[cfg(feature = "bincode")]
+fn bincode_rejects_malformed_input() {
+    use mutatis::codec::Bincode;
+
+    // A length prefix claiming far more elements than there are bytes.
+    let bytes = u64::MAX.to_le_bytes();
+    assert_eq!(<Bincode as Codec<Vec<u64>>>::decode(&bytes), None);
+}
+
+#[test]
+#[cfg(feature = "postcard")]
+fn postcard_roundtrip() {
+    use mutatis::codec::Postcard;
+    roundtrip::<_, Postcard>(structured());
+    roundtrip::<_, Postcard>((1_u8, true, 'x'));
+}
+
+#[test]
+#[cfg(feature = "postcard")]
+fn postcard_rejects_malformed_input() {
+    use mutatis::codec::Postcard;
+    assert_eq!(<Postcard as Codec<String>>::decode(&[5, b'a']), None);
+}
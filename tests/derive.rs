@@ -185,3 +185,97 @@ fn no_default_mutator() -> anyhow::Result<()> {
     session.mutate_with(&mut mutator, &mut value)?;
     Ok(())
 }
+
+#[test]
+fn derive_on_recursive_enum() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, Mutate)]
+    enum Expr {
+        Lit(u8),
+        Add(Box<Expr>, Box<Expr>),
+    }
+
+    fn lit(x: u8) -> Box<Expr> {
+        Box::new(Expr::Lit(x))
+    }
+
+    let mut session = Session::new();
+    let mut value = Expr::Add(lit(1), Box::new(Expr::Add(lit(2), lit(3))));
+    for _ in 0..10 {
+        session.mutate(&mut value)?;
+    }
+
+    // With a maximum depth of one, only the outer `Add`'s operands are
+    // mutated, and the inner `Add`'s operands are left untouched.
+    let mut session = Session::new().max_depth(1);
+    let mut value = Expr::Add(lit(1), Box::new(Expr::Add(lit(2), lit(3))));
+    for _ in 0..10 {
+        session.mutate(&mut value)?;
+    }
+    let Expr::Add(_, inner) = &value else {
+        unreachable!()
+    };
+    assert_eq!(**inner, Expr::Add(lit(2), lit(3)));
+    Ok(())
+}
+
+#[test]
+fn derive_on_generic_recursive_enum() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, Mutate)]
+    enum List<T> {
+        Nil,
+        Cons(T, Box<List<T>>),
+    }
+
+    fn cons<T>(x: T, tail: List<T>) -> List<T> {
+        List::Cons(x, Box::new(tail))
+    }
+
+    let mut session = Session::new();
+    let mut value = cons(1u8, cons(2u8, List::Nil));
+    for _ in 0..10 {
+        session.mutate(&mut value)?;
+    }
+
+    let mut session = Session::new();
+    let mut value = cons((true, 'x'), List::Nil);
+    for _ in 0..10 {
+        session.mutate(&mut value)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn derive_with_option_of_derived_struct() -> anyhow::Result<()> {
+    use mutatis::{Context, Generate};
+
+    #[derive(Debug, Default, PartialEq, Mutate)]
+    struct Inner {
+        x: u8,
+    }
+
+    // Derived mutators cannot generate values from scratch, which `Option`'s
+    // mutator requires. Note that `Inner` does not implement `SizeHint`.
+    impl Generate<Inner> for InnerMutator<m::U8> {
+        fn generate(&mut self, ctx: &mut Context) -> mutatis::Result<Inner> {
+            Ok(Inner {
+                x: m::u8().generate(ctx)?,
+            })
+        }
+    }
+
+    #[derive(Debug, Default, Mutate)]
+    struct Outer {
+        inner: Option<Inner>,
+        y: bool,
+    }
+
+    let mut session = Session::new().max_size(64);
+    let mut value = Outer::default();
+    let mut grew = false;
+    for _ in 0..100 {
+        session.mutate(&mut value)?;
+        grew |= value.inner.is_some();
+    }
+    assert!(grew);
+    Ok(())
+}
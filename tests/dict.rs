@@ -0,0 +1,63 @@
+#![cfg(feature = "alloc")]
+
+use mutatis::{dict::Dict, mutators as m, Session};
+
+#[test]
+fn parse_dict() {
+    let dict = Dict::parse(
+        r#"
+        # A comment.
+        kw1="SELECT"
+        kw2@1 = "FROM"
+        "\x00\xffab"
+        quote="\"\\"
+
+        "SELECT"
+        "#,
+    )
+    .unwrap();
+    let tokens = dict.iter().collect::<Vec<_>>();
+    assert_eq!(tokens, [&b"SELECT"[..], b"FROM", b"\x00\xffab", b"\"\\"]);
+}
+
+#[test]
+fn parse_dict_errors() {
+    for bad in [
+        "SELECT",
+        "kw=SELECT",
+        "kw \"SELECT\"",
+        "\"SELECT",
+        "\"\\x0\"",
+        "\"\\n\"",
+        "\"SELECT\" trailing",
+        "bad-name=\"SELECT\"",
+    ] {
+        let err = Dict::parse(bad).unwrap_err();
+        assert!(err.is_other(), "{bad}: {err}");
+    }
+}
+
+#[test]
+fn dict_tokens_are_spliced_into_ints() {
+    let dict = Dict::from_iter([[0xca, 0xfe, 0xba, 0xbe]]);
+    let mut session = Session::new().seed(0x1234).dict(dict);
+    let mut seen_le = false;
+    let mut seen_be = false;
+    let mut value = 0u32;
+    for _ in 0..1000 {
+        session.mutate_with(&mut m::u32(), &mut value).unwrap();
+        seen_le |= value == 0xbebafeca;
+        seen_be |= value == 0xcafebabe;
+    }
+    assert!(seen_le && seen_be);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn dict_from_file() {
+    let path = std::env::temp_dir().join(format!("mutatis-test-{}.dict", std::process::id()));
+    std::fs::write(&path, "magic=\"\\x7fELF\"\n").unwrap();
+    let dict = Dict::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(dict.iter().collect::<Vec<_>>(), [b"\x7fELF"]);
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "check")]
+
+use mutatis::{check::Check, mutators as m};
+
+#[test]
+fn differential_agreeing_implementations() {
+    Check::new()
+        .run_differential(
+            m::u32(),
+            [0, 1, u32::MAX],
+            |x: &u32| x.count_ones(),
+            |x: &u32| (0..32).filter(|i| x & (1 << i) != 0).count() as u32,
+        )
+        .unwrap();
+}
+
+#[test]
+fn differential_reports_both_outputs_for_shrunken_input() {
+    let failure = Check::new()
+        .shrink_until_fixpoint(true)
+        .run_differential(m::u32(), [u32::MAX], |x: &u32| *x, |x: &u32| (*x).min(100))
+        .unwrap_err()
+        .unwrap_failed();
+
+    assert!(failure.value > 100);
+    assert!(failure.value < u32::MAX);
+    assert_eq!(
+        failure.message,
+        format!(
+            "implementations diverged: implementation A returned {}, \
+             implementation B returned 100",
+            failure.value
+        )
+    );
+}
+
+#[test]
+fn differential_panic_in_one_implementation_diverges() {
+    let failure = Check::new()
+        .run_differential(
+            m::u32(),
+            [u32::MAX],
+            |x: &u32| *x,
+            |x: &u32| {
+                assert!(*x <= 100, "boom");
+                *x
+            },
+        )
+        .unwrap_err()
+        .unwrap_failed();
+
+    assert!(failure.value > 100);
+    assert_eq!(
+        failure.message,
+        format!(
+            "implementations diverged: implementation A returned {}, \
+             implementation B panicked: boom",
+            failure.value
+        )
+    );
+}
+
+#[test]
+fn differential_panic_in_both_implementations_agrees() {
+    Check::new()
+        .run_differential(
+            m::u32(),
+            [0, u32::MAX],
+            |x: &u32| {
+                assert!(*x <= 100, "boom");
+                *x
+            },
+            |x: &u32| {
+                assert!(*x <= 100, "bang");
+                *x
+            },
+        )
+        .unwrap();
+}
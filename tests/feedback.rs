@@ -0,0 +1,74 @@
+#![cfg(feature = "check")]
+
+use mutatis::{
+    check::{Check, CheckError},
+    mutators as m,
+};
+
+#[test]
+fn feedback_keeps_inputs_with_novel_labels() {
+    let (result, stats) = Check::new()
+        .iters(1_000)
+        .seed(0x1234)
+        .run_with_feedback_and_stats(m::u32(), [0], |x: &u32| Ok::<_, String>([*x % 4]));
+    result.unwrap();
+
+    // The initial corpus entry produces the label `0` and each of the other
+    // three labels is novel exactly once, so the corpus ends up with one entry
+    // per label.
+    let sizes = &stats.corpus_sizes;
+    assert_eq!(sizes[0], 1);
+    assert_eq!(*sizes.last().unwrap(), 4);
+    assert!(sizes.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn feedback_without_novelty_keeps_corpus_size() {
+    let (result, stats) =
+        Check::new()
+            .iters(1_000)
+            .run_with_feedback_and_stats(m::u32(), [0, 1, 2], |_: &u32| Ok::<_, String>(["same"]));
+    result.unwrap();
+    assert!(stats.corpus_sizes.iter().all(|n| *n == 3));
+}
+
+#[test]
+fn feedback_finds_and_shrinks_failures() {
+    let failure = Check::new()
+        .seed(0x1234)
+        .run_with_feedback(m::u32(), [0], |x: &u32| {
+            if *x >= 1_000_000 {
+                Err(format!("{x} is too large"))
+            } else {
+                Ok([*x < 1_000])
+            }
+        })
+        .unwrap_err()
+        .unwrap_failed();
+
+    assert!(failure.value >= 1_000_000);
+    assert_eq!(failure.message, format!("{} is too large", failure.value));
+}
+
+#[test]
+fn feedback_checks_initial_corpus() {
+    let failure = Check::new()
+        .iters(0)
+        .run_with_feedback(m::u32(), [1, 2, 3], |x: &u32| {
+            if *x == 2 {
+                Err("two")
+            } else {
+                Ok([*x])
+            }
+        })
+        .unwrap_err()
+        .unwrap_failed();
+    assert_eq!(failure.value, 2);
+    assert_eq!(failure.message, "two");
+}
+
+#[test]
+fn feedback_empty_corpus() {
+    let result = Check::new().run_with_feedback(m::u32(), [], |_: &u32| Ok::<_, String>([()]));
+    assert!(matches!(result, Err(CheckError::EmptyCorpus)));
+}
@@ -0,0 +1,129 @@
+use mutatis::{mutators as m, Mutate, Session};
+
+#[test]
+fn filter_maintains_invariant() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::tuple2(m::u8(), m::u8()).filter(|(a, b)| a <= b);
+    let mut value = (0, u8::MAX);
+    for _ in 0..1000 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        assert!(value.0 <= value.1);
+    }
+}
+
+#[test]
+fn filter_restores_value_when_exhausted() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::bool().filter(|_| false).retries(10);
+    let mut value = true;
+    let err = session.mutate_with(&mut mutator, &mut value).unwrap_err();
+    assert!(err.is_exhausted());
+    assert!(value);
+}
+
+#[test]
+fn filter_restores_value_when_mutator_fails() {
+    use mutatis::{Candidates, Error, Result};
+
+    struct Fails;
+    impl Mutate<u8> for Fails {
+        fn mutate(&mut self, c: &mut Candidates, value: &mut u8) -> Result<()> {
+            c.mutation(|_| {
+                *value = 99;
+                Err(Error::other("oops"))
+            })
+        }
+    }
+
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = Fails.filter(|_| true);
+    let mut value = 5;
+    let err = session.mutate_with(&mut mutator, &mut value).unwrap_err();
+    assert!(err.is_other());
+    assert_eq!(value, 5);
+}
+
+#[test]
+fn filter_only_snapshots_when_its_mutation_is_chosen() {
+    use mutatis::{Candidates, Result};
+    use std::cell::Cell;
+
+    thread_local! {
+        static CLONES: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(Debug)]
+    struct Counted(u8);
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            CLONES.with(|c| c.set(c.get() + 1));
+            Counted(self.0)
+        }
+    }
+
+    // Many candidates that do not involve the filter at all.
+    struct Reset;
+    impl Mutate<Counted> for Reset {
+        fn mutate(&mut self, c: &mut Candidates, value: &mut Counted) -> Result<()> {
+            for _ in 0..100 {
+                c.mutation(|_| {
+                    value.0 = 0;
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        }
+    }
+
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::u8()
+        .proj(|c: &mut Counted| &mut c.0)
+        .filter(|c: &Counted| c.0 != 1)
+        .or(Reset);
+    let mut value = Counted(0);
+    for _ in 0..1000 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        assert_ne!(value.0, 1);
+    }
+    let clones = CLONES.with(|c| c.get());
+    assert!(clones > 0);
+    assert!(clones < 200, "cloned {clones} times");
+}
+
+#[test]
+fn exhausted_single_candidate_is_applied_once() {
+    use mutatis::{Candidates, Error, Result};
+
+    struct Exhausted(usize);
+    impl Mutate<u8> for Exhausted {
+        fn mutate(&mut self, c: &mut Candidates, _value: &mut u8) -> Result<()> {
+            c.mutation(|_| {
+                self.0 += 1;
+                Err(Error::exhausted())
+            })
+        }
+    }
+
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = Exhausted(0);
+    let err = session.mutate_with(&mut mutator, &mut 0).unwrap_err();
+    assert!(err.is_exhausted());
+    assert_eq!(mutator.0, 1);
+}
+
+#[test]
+fn filter_retries_are_bounded() {
+    let mut session = Session::new().seed(0x1234);
+    let calls = std::cell::Cell::new(0);
+    let mut mutator = m::bool()
+        .filter(|_| {
+            calls.set(calls.get() + 1);
+            false
+        })
+        .retries(100);
+    let err = session.mutate_with(&mut mutator, &mut true).unwrap_err();
+    assert!(err.is_exhausted());
+
+    // The first mutation, and then one per retry.
+    assert_eq!(calls.get(), 101);
+}
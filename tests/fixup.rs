@@ -0,0 +1,54 @@
+use mutatis::{mutators as m, Candidates, Error, Mutate, Result, Session};
+
+/// A mutator whose only candidate scribbles on the value and then fails.
+struct Scribble;
+
+impl Mutate<(u8, u8)> for Scribble {
+    fn mutate(&mut self, c: &mut Candidates, value: &mut (u8, u8)) -> Result<()> {
+        c.mutation(|_| {
+            value.0 = 1;
+            Err(Error::other("oops"))
+        })
+    }
+}
+
+fn fix_sum((a, b): &mut (u8, u8)) {
+    *b = u8::MAX - *a;
+}
+
+#[test]
+fn fixup_after_every_mutation() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::fixup(m::default::<(u8, u8)>(), fix_sum);
+    let mut value = (0, u8::MAX);
+    for _ in 0..100 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        assert_eq!(value.0.wrapping_add(value.1), u8::MAX);
+    }
+}
+
+#[test]
+fn fixup_after_failed_mutation() {
+    let mut session = Session::new();
+    let mut mutator = m::fixup(Scribble, fix_sum);
+    let mut value = (0, u8::MAX);
+    let err = session.mutate_with(&mut mutator, &mut value).unwrap_err();
+    assert!(err.is_other());
+    assert_eq!(value, (1, u8::MAX - 1));
+}
+
+#[test]
+fn fixup_generated_values() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::option(m::fixup(m::u8(), |x: &mut u8| *x |= 1));
+    let mut generated = 0;
+    for _ in 0..100 {
+        let mut value = None;
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        if let Some(x) = value {
+            assert_eq!(x % 2, 1);
+            generated += 1;
+        }
+    }
+    assert!(generated > 0);
+}
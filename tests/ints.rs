@@ -0,0 +1,29 @@
+use mutatis::{mutators as m, Session};
+
+#[test]
+fn int_mutators_find_boundary_values() {
+    let mut session = Session::new().seed(0x1234);
+    let mut value = 1234i32;
+    let mut seen = [false; 5];
+    for _ in 0..10_000 {
+        session.mutate_with(&mut m::i32(), &mut value).unwrap();
+        for (seen, boundary) in seen.iter_mut().zip([0, -1, i32::MIN, i32::MAX, 1 << 16]) {
+            *seen |= value == boundary;
+        }
+    }
+    assert_eq!(seen, [true; 5]);
+}
+
+#[test]
+fn int_mutators_step_by_small_amounts() {
+    let mut session = Session::new().seed(0x1234);
+    let mut seen_inc = false;
+    let mut seen_dec = false;
+    for _ in 0..1000 {
+        let mut value = 1000u16;
+        session.mutate_with(&mut m::u16(), &mut value).unwrap();
+        seen_inc |= (1001..=1035).contains(&value);
+        seen_dec |= (965..=999).contains(&value);
+    }
+    assert!(seen_inc && seen_dec);
+}
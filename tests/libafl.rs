@@ -0,0 +1,119 @@
+#![cfg(all(feature = "libafl", feature = "derive"))]
+
+use libafl::{
+    corpus::CorpusId,
+    mutators::{MutationResult, Mutator},
+    state::HasRand,
+};
+use libafl_bolts::{rands::StdRand, Named};
+use mutatis::{libafl::MutatisMutator, mutators as m, Candidates, Mutate, Session};
+
+/// A minimal LibAFL state that only provides randomness.
+struct State(StdRand);
+
+impl HasRand for State {
+    type Rand = StdRand;
+
+    fn rand(&self) -> &StdRand {
+        &self.0
+    }
+
+    fn rand_mut(&mut self) -> &mut StdRand {
+        &mut self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Mutate)]
+struct MyInput {
+    x: u32,
+    y: u32,
+}
+
+#[test]
+fn mutates_input() {
+    let mut state = State(StdRand::with_seed(1));
+    let mut mutator = MutatisMutator::new(m::default::<MyInput>());
+
+    let mut input = MyInput { x: 0, y: 0 };
+    let result = mutator.mutate(&mut state, &mut input).unwrap();
+    assert_eq!(result, MutationResult::Mutated);
+    assert_ne!(input, MyInput { x: 0, y: 0 });
+}
+
+#[test]
+fn exhausted_is_skipped() {
+    let mut state = State(StdRand::with_seed(1));
+    /// A mutator that never registers any candidate mutations.
+    struct Nothing;
+    impl Mutate<u32> for Nothing {
+        fn mutate(&mut self, _c: &mut Candidates, _value: &mut u32) -> mutatis::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut mutator = MutatisMutator::new(Nothing);
+
+    let mut input = 42;
+    let result = mutator.mutate(&mut state, &mut input).unwrap();
+    assert_eq!(result, MutationResult::Skipped);
+}
+
+#[test]
+fn deterministic_given_state_seed() {
+    let run = || {
+        let mut state = State(StdRand::with_seed(1234));
+        let mut mutator = MutatisMutator::new(m::u64());
+        let mut input = 0_u64;
+        for _ in 0..10 {
+            mutator.mutate(&mut state, &mut input).unwrap();
+        }
+        input
+    };
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn keeps_session_state_across_mutations() {
+    let mut state = State(StdRand::with_seed(1));
+    let mut mutator = MutatisMutator::new(m::u32());
+    mutator.session_mut().cmp_log().observe_u32(42, 0xcafebabe);
+
+    let found = (0..1000).any(|_| {
+        let mut input = 42_u32;
+        mutator.mutate(&mut state, &mut input).unwrap();
+        input == 0xcafebabe
+    });
+    assert!(found);
+}
+
+#[test]
+fn post_exec_reports_feedback_for_adaptive_scheduling() {
+    // When mutating `Some(_)`, the option mutator may either mutate the inner
+    // value or replace it with `None`. Only add the latter to the corpus.
+    let mut state = State(StdRand::with_seed(1));
+    let mut mutator =
+        MutatisMutator::new(m::option(m::just(true))).with_session(Session::new().adaptive(true));
+    for _ in 0..1000 {
+        let mut input = Some(false);
+        mutator.mutate(&mut state, &mut input).unwrap();
+        let new_corpus_id = input.is_none().then_some(CorpusId(0));
+        mutator.post_exec(&mut state, new_corpus_id).unwrap();
+    }
+
+    let mut none = 0;
+    for _ in 0..1000 {
+        let mut input = Some(false);
+        mutator.mutate(&mut state, &mut input).unwrap();
+        none += u32::from(input.is_none());
+    }
+    assert!(none > 750, "none = {none}");
+}
+
+#[test]
+fn name() {
+    let mutator = MutatisMutator::new(m::u8());
+    assert_eq!(mutator.name(), "MutatisMutator");
+
+    let mutator = mutator.with_name("MyMutator");
+    assert_eq!(mutator.name(), "MyMutator");
+}
@@ -0,0 +1,267 @@
+use mutatis::{mutators as m, Candidates, Context, Error, Generate, Mutate, Result, Session};
+
+/// A mutator that never has any candidate mutations.
+struct Exhausted;
+
+impl Mutate<u8> for Exhausted {
+    fn mutate(&mut self, _c: &mut Candidates, _value: &mut u8) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Generate<u8> for Exhausted {
+    fn generate(&mut self, _ctx: &mut Context) -> Result<u8> {
+        Err(Error::exhausted())
+    }
+}
+
+/// Like `m::just`, but exhausted when there is no value.
+#[cfg(feature = "alloc")]
+struct MaybeJust(Option<u8>);
+
+#[cfg(feature = "alloc")]
+impl Mutate<u8> for MaybeJust {
+    fn mutate(&mut self, c: &mut Candidates, value: &mut u8) -> Result<()> {
+        match self.0 {
+            Some(x) => c.mutation(|_| {
+                *value = x;
+                Ok(())
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Generate<u8> for MaybeJust {
+    fn generate(&mut self, _ctx: &mut Context) -> Result<u8> {
+        self.0.ok_or_else(Error::exhausted)
+    }
+}
+
+#[test]
+fn one_of_skips_exhausted_mutators() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::one_of((Exhausted, m::just(42)));
+    let mut value = 0;
+    for _ in 0..100 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        assert_eq!(value, 42);
+    }
+}
+
+#[test]
+fn one_of_all_exhausted() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::one_of([m::unit(), m::unit()]);
+    let err = session.mutate_with(&mut mutator, &mut ()).unwrap_err();
+    assert!(err.is_exhausted());
+}
+
+#[test]
+fn one_of_chooses_every_mutator() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::one_of([m::just(0), m::just(1), m::just(2)]);
+    let mut seen = [false; 3];
+    let mut value = 0;
+    for _ in 0..100 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        seen[value] = true;
+    }
+    assert_eq!(seen, [true; 3]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn frequency_never_chooses_zero_weight() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::frequency([(0, m::just(0)), (1, m::just(1)), (3, m::just(2))]);
+    let mut counts = [0; 3];
+    let mut value = 0;
+    for _ in 0..1000 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        counts[value] += 1;
+    }
+    assert_eq!(counts[0], 0);
+    assert!(counts[2] > counts[1]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn frequency_with_huge_weights() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::frequency([
+        (u32::MAX, m::just(0u8)),
+        (u32::MAX, m::just(1u8)),
+        (1, m::just(2u8)),
+    ]);
+    let mut seen = [false; 3];
+    let mut value = 0;
+    for _ in 0..100 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        seen[usize::from(value)] = true;
+    }
+    assert!(seen[0] && seen[1]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn one_of_nested_huge_weights() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::one_of((
+        m::frequency([(u32::MAX, m::just(0u8)), (1, m::just(1u8))]),
+        m::frequency([(u32::MAX, m::just(2u8)), (u32::MAX, m::just(3u8))]),
+    ));
+    let mut value = 0;
+    for _ in 0..100 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+    }
+}
+
+#[test]
+fn one_of_generate_falls_back_from_exhausted_generators() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::option(m::one_of((Exhausted, m::just(42), Exhausted)));
+    for _ in 0..100 {
+        let mut value = None;
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        assert_eq!(value, Some(42));
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn frequency_generate_falls_back_from_exhausted_generators() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::option(m::frequency([
+        (100, MaybeJust(None)),
+        (0, MaybeJust(Some(0))),
+        (1, MaybeJust(Some(42))),
+    ]));
+    for _ in 0..100 {
+        let mut value = None;
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        assert_eq!(value, Some(42));
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn one_of_generate_falls_back_from_recursive_at_max_depth() {
+    let mut session = Session::new().seed(0x1234).max_depth(0);
+    let mut mutator = m::option(m::one_of((m::recursive(|_| m::just(1u8)), m::just(2u8))));
+    fn assert_send(_: &impl Send) {}
+    assert_send(&mutator);
+    for _ in 0..100 {
+        let mut value = None;
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        assert_eq!(value, Some(2));
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn frequency_weights_only_non_exhausted_mutators() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::frequency([
+        (100, MaybeJust(None)),
+        (3, MaybeJust(Some(0))),
+        (1, MaybeJust(Some(1))),
+    ]);
+    let mut zeros = 0;
+    for _ in 0..4000 {
+        let mut value = 42;
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        zeros += u32::from(value == 0);
+    }
+    assert!((2700..3300).contains(&zeros), "zeros = {zeros}");
+}
+
+/// A mutator with `n` candidate mutations, the `i`th of which sets the value
+/// to `f(i, value)`.
+struct Many<F>(u32, F);
+
+impl<F> Mutate<u32> for Many<F>
+where
+    F: Fn(u32, u32) -> u32,
+{
+    fn mutate(&mut self, c: &mut Candidates, value: &mut u32) -> Result<()> {
+        for i in 0..self.0 {
+            c.mutation(|_| {
+                *value = (self.1)(i, *value);
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn one_of_weights_are_independent_of_candidate_counts() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::one_of((m::just(0), Many(99, |i, _| i + 1)));
+    let mut zeros = 0;
+    for _ in 0..4000 {
+        let mut value = 42;
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        zeros += u32::from(value == 0);
+    }
+    assert!((1800..2200).contains(&zeros), "zeros = {zeros}");
+}
+
+#[test]
+fn one_of_mutators_are_one_candidate_each_among_siblings() {
+    let mut session = Session::new().seed(0x1234);
+    let mut mutator = m::one_of((m::just(0), Many(99, |i, _| i + 1))).or(m::just(1000));
+    let mut thousands = 0;
+    for _ in 0..4000 {
+        let mut value = 42;
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        thousands += u32::from(value == 1000);
+    }
+    assert!((1200..1450).contains(&thousands), "thousands = {thousands}");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn reducer_enumerates_one_of_candidates() {
+    use mutatis::reduce::Reducer;
+
+    // Only one candidate of one of the mutators reduces the value, and random
+    // shrinking is unlikely to find it, but sweeping over every candidate
+    // will.
+    let mutator = m::one_of((
+        Many(1000, |_, x| x),
+        Many(1000, |i, x: u32| if i == 123 { x.min(7) } else { x }),
+    ));
+    let reduced = Reducer::new()
+        .seed(0x1234)
+        .reduce(mutator, 100, |_: &u32| true);
+    assert_eq!(reduced, 7);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn adaptive_scheduling_sees_one_of_candidates() {
+    // Half of the time, the first mutator is chosen, and then it either
+    // mutates the inner value or replaces the option with `None`. Reward only
+    // the latter.
+    let mut mutator = m::one_of((m::option(m::just(true)), m::just(Some(true))));
+
+    let mut session = Session::new().seed(0x1234).adaptive(true);
+    for _ in 0..1000 {
+        let mut value = Some(false);
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        session.feedback(value.is_none());
+    }
+
+    // Without feedback, we would only expect a quarter of mutations to produce
+    // `None`.
+    let mut none = 0;
+    for _ in 0..1000 {
+        let mut value = Some(false);
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        none += u32::from(value.is_none());
+    }
+    assert!(none > 400, "none = {none}");
+}
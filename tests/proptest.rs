@@ -0,0 +1,54 @@
+#![cfg(feature = "proptest")]
+
+use mutatis::{mutators as m, proptest::MutatisStrategy};
+use proptest::{
+    prelude::*,
+    strategy::ValueTree,
+    test_runner::{TestError, TestRunner},
+};
+
+proptest! {
+    #[test]
+    fn strategy_okay(x in MutatisStrategy::new(m::u8(), [0, 1, 2])) {
+        prop_assert_eq!(x.wrapping_add(0), x);
+    }
+}
+
+#[test]
+fn strategy_fail_and_shrink() {
+    let mut runner = TestRunner::deterministic();
+    let result = runner.run(&MutatisStrategy::new(m::u8(), [u8::MAX]), |x| {
+        prop_assert!(x < 10);
+        Ok(())
+    });
+    match result {
+        Err(TestError::Fail(_, value)) => assert_eq!(value, 10),
+        other => panic!("expected a failure, got {other:?}"),
+    }
+}
+
+#[test]
+fn value_tree_simplify_and_complicate() {
+    let mut runner = TestRunner::deterministic();
+    let strategy = MutatisStrategy::new(m::u32(), [u32::MAX]).max_mutations(0);
+    let mut tree = strategy.new_tree(&mut runner).unwrap();
+    assert_eq!(tree.current(), u32::MAX);
+
+    assert!(tree.simplify());
+    let simplified = tree.current();
+    assert!(simplified < u32::MAX);
+
+    assert!(tree.complicate());
+    assert_eq!(tree.current(), u32::MAX);
+    assert!(!tree.complicate());
+}
+
+#[test]
+fn value_tree_stops_when_exhausted() {
+    let mut runner = TestRunner::deterministic();
+    let strategy = MutatisStrategy::new(m::bool(), [false]).max_mutations(0);
+    let mut tree = strategy.new_tree(&mut runner).unwrap();
+    assert!(!tree.simplify());
+    assert!(!tree.simplify());
+    assert!(!tree.current());
+}
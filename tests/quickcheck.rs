@@ -0,0 +1,120 @@
+#![cfg(feature = "quickcheck")]
+
+use mutatis::{quickcheck::Mutated, Candidates, Context, DefaultMutate, Generate, Mutate};
+use quickcheck::{Arbitrary, Gen, QuickCheck};
+use std::cell::Cell;
+
+#[test]
+fn arbitrary_is_deterministic_given_gen_seed() {
+    let gen = || Mutated::<u64>::arbitrary(&mut Gen::from_size_and_seed(100, 42));
+    assert_eq!(gen(), gen());
+}
+
+#[test]
+fn shrink_yields_smaller_values() {
+    let value = Mutated(1000_u32);
+    let shrunk = value.shrink().collect::<Vec<_>>();
+    assert!(!shrunk.is_empty());
+    assert!(shrunk.iter().all(|x| *x < value));
+}
+
+#[test]
+fn shrink_is_deterministic() {
+    let shrink = || Mutated(1000_u32).shrink().collect::<Vec<_>>();
+    assert_eq!(shrink(), shrink());
+}
+
+#[test]
+fn arbitrary_uses_gen_size_as_max_size() {
+    /// A value that records the maximum size it was generated with.
+    #[derive(Clone, Debug, PartialEq)]
+    struct MaxSize(Option<usize>);
+
+    #[derive(Default)]
+    struct MaxSizeMutator;
+
+    impl Mutate<MaxSize> for MaxSizeMutator {
+        fn mutate(&mut self, _c: &mut Candidates, _value: &mut MaxSize) -> mutatis::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Generate<MaxSize> for MaxSizeMutator {
+        fn generate(&mut self, ctx: &mut Context) -> mutatis::Result<MaxSize> {
+            Ok(MaxSize(ctx.max_size()))
+        }
+    }
+
+    impl DefaultMutate for MaxSize {
+        type DefaultMutate = MaxSizeMutator;
+    }
+
+    let value = Mutated::<MaxSize>::arbitrary(&mut Gen::new(42));
+    assert_eq!(value.0 .0, Some(42));
+}
+
+#[test]
+fn shrink_stops_when_exhausted() {
+    assert_eq!(Mutated(0_u32).shrink().count(), 0);
+}
+
+#[test]
+fn shrink_skips_unchanged_values() {
+    /// A value whose only shrinking mutation leaves it unchanged.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Stuck(u32);
+
+    #[derive(Default)]
+    struct StuckMutator;
+
+    impl Mutate<Stuck> for StuckMutator {
+        fn mutate(&mut self, c: &mut Candidates, _value: &mut Stuck) -> mutatis::Result<()> {
+            c.mutation(|_| Ok(()))
+        }
+    }
+
+    impl Generate<Stuck> for StuckMutator {
+        fn generate(&mut self, _ctx: &mut Context) -> mutatis::Result<Stuck> {
+            Ok(Stuck(0))
+        }
+    }
+
+    impl DefaultMutate for Stuck {
+        type DefaultMutate = StuckMutator;
+    }
+
+    assert_eq!(Mutated(Stuck(42)).shrink().count(), 0);
+}
+
+#[test]
+fn quickcheck_property_okay() {
+    fn prop(x: Mutated<u32>) -> bool {
+        x.wrapping_mul(2) / 2 <= *x
+    }
+    QuickCheck::new().quickcheck(prop as fn(Mutated<u32>) -> bool);
+}
+
+#[test]
+fn quickcheck_property_fail_and_shrink() {
+    thread_local! {
+        static LAST_FAILURE: Cell<Option<u32>> = const { Cell::new(None) };
+    }
+
+    fn prop(x: Mutated<u32>) -> bool {
+        let ok = *x < 10;
+        if !ok {
+            LAST_FAILURE.with(|f| f.set(Some(*x)));
+        }
+        ok
+    }
+
+    let result = QuickCheck::new()
+        .rng(Gen::from_size_and_seed(100, 1234))
+        .quicktest(prop as fn(Mutated<u32>) -> bool)
+        .unwrap_err();
+    assert!(result.is_failure());
+
+    // `quickcheck` greedily accepts each failing shrunken input, so the last
+    // failing input is the one that it reports.
+    assert_eq!(LAST_FAILURE.with(|f| f.get()), Some(10));
+}
@@ -0,0 +1,138 @@
+#![cfg(feature = "std")]
+
+use mutatis::{mutators as m, reduce::Reducer, Mutate};
+use std::time::Duration;
+
+#[test]
+fn reduce_to_minimum() {
+    let reduced = Reducer::new()
+        .seed(0x1234)
+        .reduce(m::u8(), u8::MAX, |x: &u8| *x >= 10);
+    assert_eq!(reduced, 10);
+}
+
+#[test]
+fn reduce_pair_to_minimum() {
+    let reduced = Reducer::new().reduce(
+        m::tuple2(m::u8(), m::bool()),
+        (u8::MAX, true),
+        |(x, _): &(u8, bool)| *x >= 42,
+    );
+    assert_eq!(reduced, (42, false));
+}
+
+#[test]
+fn reduce_respects_max_iters() {
+    let mut checked = 0;
+    let _ = Reducer::new()
+        .max_iters(5)
+        .reduce(m::u32(), u32::MAX, |_: &u32| {
+            checked += 1;
+            true
+        });
+    assert_eq!(checked, 5);
+}
+
+#[test]
+fn reduce_respects_time_limit() {
+    let reduced =
+        Reducer::new()
+            .time_limit(Duration::ZERO)
+            .reduce(m::u32(), u32::MAX, |_: &u32| true);
+    assert_eq!(reduced, u32::MAX);
+}
+
+#[test]
+fn reduce_reports_progress() {
+    let mut reductions = vec![];
+    let reduced = Reducer::new()
+        .on_progress(|progress| reductions.push(progress.reductions))
+        .reduce(m::u64(), u64::MAX, |_: &u64| true);
+    assert_eq!(reduced, 0);
+    assert!(!reductions.is_empty());
+    assert!(reductions.iter().copied().eq(1..=reductions.len()));
+}
+
+#[test]
+fn reduce_uninteresting_returns_original() {
+    let mut checked = 0;
+    let reduced = Reducer::new().reduce(m::u16(), 1234, |_: &u16| {
+        checked += 1;
+        false
+    });
+    assert_eq!(reduced, 1234);
+    assert!(checked > 0);
+}
+
+#[test]
+fn reduce_dedup_skips_recently_checked_candidates() {
+    let mut checked = std::collections::HashSet::new();
+    let reduced = Reducer::new()
+        .seed(0x1234)
+        .reduce_dedup(m::u8(), 200, |x: &u8| {
+            assert!(checked.insert(*x), "checked {x} twice");
+            *x >= 10
+        });
+    assert_eq!(reduced, 10);
+}
+
+#[test]
+fn reduce_reaches_fixpoint_when_shrinks_can_leave_value_unchanged() {
+    // Shrinking a `range` value chooses a new value in `start..=value`, which
+    // may be the value itself.
+    let mut checked = 0;
+    let reduced = Reducer::new().reduce(m::range(0u32..=10), 5, |x: &u32| {
+        checked += 1;
+        *x >= 3
+    });
+    assert_eq!(reduced, 3);
+    assert!(checked < 10_000, "checked = {checked}");
+}
+
+#[test]
+fn reduce_does_not_require_debug() {
+    // Not `Debug`: the reducer only needs to compare values for equality.
+    #[derive(Clone, PartialEq)]
+    struct Input(u32);
+
+    let reduced = Reducer::new().seed(0x1234).reduce(
+        m::u32().proj(|x: &mut Input| &mut x.0),
+        Input(1_000_000),
+        |x: &Input| x.0 >= 77,
+    );
+    assert!(reduced.0 == 77);
+}
+
+#[test]
+fn reduce_sweep_resumes_after_each_reduction() {
+    use mutatis::{Candidates, Result};
+
+    // A mutator with many candidates that leave the value unchanged and a
+    // single candidate that decrements it, so that random shrinking quickly
+    // stalls and the sweep over every candidate has to find each reduction.
+    struct Decrement;
+    impl Mutate<u32> for Decrement {
+        fn mutate(&mut self, c: &mut Candidates, value: &mut u32) -> Result<()> {
+            for _ in 0..1000 {
+                c.mutation(|_| Ok(()))?;
+            }
+            if *value > 0 {
+                c.mutation(|_| Ok(*value -= 1))?;
+            }
+            Ok(())
+        }
+    }
+
+    let mut checked = 0;
+    let reduced = Reducer::new()
+        .seed(0x1234)
+        .reduce(Decrement, 100, |_: &u32| {
+            checked += 1;
+            true
+        });
+    assert_eq!(reduced, 0);
+
+    // Each reduction that the sweep finds must not restart random shrinking,
+    // which would cost at least a thousand more checks per reduction.
+    assert!(checked < 10_000, "checked = {checked}");
+}
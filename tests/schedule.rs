@@ -0,0 +1,74 @@
+#![cfg(feature = "alloc")]
+
+use mutatis::{mutators as m, Candidates, Mutate, Result, Session};
+
+struct GoodOrBad;
+
+impl Mutate<u32> for GoodOrBad {
+    fn mutate(&mut self, c: &mut Candidates, value: &mut u32) -> Result<()> {
+        c.named_mutation("good", |_| {
+            *value = 1;
+            Ok(())
+        })?;
+        c.named_mutation("bad", |_| {
+            *value = 2;
+            Ok(())
+        })
+    }
+}
+
+#[test]
+fn adaptive_scheduling_favors_interesting_candidates() {
+    let mut mutator = GoodOrBad;
+
+    let mut session = Session::new().seed(0x1234).adaptive(true);
+    let mut value = 0;
+    for _ in 0..1000 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        session.feedback(value == 1);
+    }
+
+    let mut good = 0;
+    for _ in 0..1000 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        good += u32::from(value == 1);
+    }
+    assert!(good > 750, "good = {good}");
+}
+
+#[test]
+fn adaptive_scheduling_of_built_in_mutators() {
+    // When mutating `Some(_)`, the option mutator may either mutate the inner
+    // value or replace it with `None`. Reward only the latter.
+    let mut mutator = m::option(m::just(true));
+
+    let mut session = Session::new().seed(0x1234).adaptive(true);
+    for _ in 0..1000 {
+        let mut value = Some(false);
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        session.feedback(value.is_none());
+    }
+
+    let mut none = 0;
+    for _ in 0..1000 {
+        let mut value = Some(false);
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        none += u32::from(value.is_none());
+    }
+    assert!(none > 750, "none = {none}");
+}
+
+#[test]
+fn feedback_without_adaptive_scheduling_is_ignored() {
+    let mut mutator = GoodOrBad;
+
+    let mut session = Session::new().seed(0x1234);
+    let mut value = 0;
+    let mut good = 0;
+    for _ in 0..1000 {
+        session.mutate_with(&mut mutator, &mut value).unwrap();
+        good += u32::from(value == 1);
+        session.feedback(value == 1);
+    }
+    assert!((400..600).contains(&good), "good = {good}");
+}
@@ -0,0 +1,122 @@
+use mutatis::{mutators as m, Session, SizeHint};
+
+#[test]
+fn size_hint_of_compound_values() {
+    assert_eq!(0u32.size_hint(), 4);
+    assert_eq!((1u8, [2u16; 3]).size_hint(), 7);
+    assert_eq!(Some(1u64).size_hint(), 8);
+    assert_eq!(None::<u64>.size_hint(), 0);
+}
+
+#[test]
+fn max_size_prevents_growth() {
+    let mut session = Session::new().seed(0x1234).max_size(4);
+    let mut value = (0u32, None::<u32>);
+    for _ in 0..100 {
+        session.mutate_sized(&mut value).unwrap();
+        assert!(value.1.is_none());
+    }
+}
+
+#[test]
+fn max_size_allows_growth_within_budget() {
+    let mut session = Session::new().seed(0x1234).max_size(8);
+    let mut value = (0u32, None::<u32>);
+    let mut grew = false;
+    for _ in 0..100 {
+        session.mutate_sized(&mut value).unwrap();
+        assert!(value.size_hint() <= 8);
+        grew |= value.1.is_some();
+    }
+    assert!(grew);
+}
+
+#[test]
+fn max_size_exhausts_growth_only_mutators() {
+    let mut session = Session::new().max_size(0);
+    let mut value = None::<u32>;
+    let err = session
+        .mutate_with(&mut m::some(m::u32()), &mut value)
+        .unwrap_err();
+    assert!(err.is_exhausted());
+}
+
+#[test]
+fn max_size_prevents_switching_to_larger_result_variant() {
+    let mut session = Session::new().seed(0x1234).max_size(4);
+    let mut mutator = m::result(m::u8(), m::u64());
+    let mut value = Ok::<u8, u64>(0);
+    for _ in 0..100 {
+        session.mutate_sized_with(&mut mutator, &mut value).unwrap();
+        assert!(value.is_ok());
+    }
+}
+
+#[test]
+fn max_size_allows_switching_result_variant_within_budget() {
+    let mut session = Session::new().seed(0x1234).max_size(8);
+    let mut mutator = m::result(m::u8(), m::u64());
+    let mut value = Ok::<u8, u64>(0);
+    let mut switched = false;
+    for _ in 0..100 {
+        session.mutate_sized_with(&mut mutator, &mut value).unwrap();
+        assert!(value.size_hint() <= 8);
+        switched |= value.is_err();
+    }
+    assert!(switched);
+}
+
+#[test]
+#[cfg(feature = "arbitrary")]
+fn max_size_bounds_generated_heap_values() {
+    let mut session = Session::new().seed(0x1234).max_size(8);
+    let mut mutator = m::option(m::size_hinted(m::from_arbitrary::<String>()));
+    let mut grew = false;
+    for _ in 0..100 {
+        let mut value = None;
+        session.mutate_sized_with(&mut mutator, &mut value).unwrap();
+        assert!(value.size_hint() <= 8, "{value:?}");
+        grew |= value.is_some_and(|s| !s.is_empty());
+    }
+    assert!(grew);
+}
+
+/// A `u32` generator that counts how many values it generated.
+struct Counting<'a>(&'a std::cell::Cell<usize>);
+
+impl mutatis::Mutate<u32> for Counting<'_> {
+    fn mutate(&mut self, _c: &mut mutatis::Candidates, _value: &mut u32) -> mutatis::Result<()> {
+        Ok(())
+    }
+}
+
+impl mutatis::Generate<u32> for Counting<'_> {
+    fn generate(&mut self, _ctx: &mut mutatis::Context) -> mutatis::Result<u32> {
+        self.0.set(self.0.get() + 1);
+        Ok(0)
+    }
+}
+
+#[test]
+fn max_size_does_not_register_option_candidates_that_cannot_fit() {
+    let generated = std::cell::Cell::new(0);
+    let mut session = Session::new().seed(0x1234).max_size(3);
+    let err = session
+        .mutate_with(&mut m::option(Counting(&generated)), &mut None)
+        .unwrap_err();
+    assert!(err.is_exhausted());
+    assert_eq!(generated.get(), 0);
+}
+
+#[test]
+fn max_size_does_not_register_result_candidates_that_cannot_fit() {
+    let generated = std::cell::Cell::new(0);
+    let mut session = Session::new().seed(0x1234).max_size(3);
+    let mut mutator = m::result(m::u8(), Counting(&generated));
+    let mut value = Ok::<u8, u32>(0);
+    for _ in 0..100 {
+        session.mutate_sized_with(&mut mutator, &mut value).unwrap();
+        assert!(value.is_ok());
+    }
+    assert_eq!(generated.get(), 0);
+}
@@ -0,0 +1,96 @@
+#![cfg(all(feature = "check", feature = "derive"))]
+
+use mutatis::{
+    check::{Check, StateMachine},
+    mutators as m, Mutate,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Mutate)]
+enum Op {
+    Push(u8),
+    Pop,
+    Len,
+}
+
+#[derive(Default)]
+struct Model(Vec<u8>);
+
+impl StateMachine<Op> for Model {
+    type Observation = Option<usize>;
+
+    fn apply(&mut self, op: &Op) -> Self::Observation {
+        match op {
+            Op::Push(x) => {
+                self.0.push(*x);
+                None
+            }
+            Op::Pop => self.0.pop().map(usize::from),
+            Op::Len => Some(self.0.len()),
+        }
+    }
+}
+
+/// A fixed-capacity stack that silently drops pushes when it is full.
+#[derive(Default)]
+struct BuggyStack {
+    elems: [u8; 4],
+    len: usize,
+}
+
+impl StateMachine<Op> for BuggyStack {
+    type Observation = Option<usize>;
+
+    fn apply(&mut self, op: &Op) -> Self::Observation {
+        match op {
+            Op::Push(x) => {
+                if self.len < self.elems.len() {
+                    self.elems[self.len] = *x;
+                    self.len += 1;
+                }
+                None
+            }
+            Op::Pop => {
+                self.len = self.len.checked_sub(1)?;
+                Some(usize::from(self.elems[self.len]))
+            }
+            Op::Len => Some(self.len),
+        }
+    }
+}
+
+fn corpus() -> Vec<Vec<Op>> {
+    vec![vec![Op::Push(1), Op::Len, Op::Pop], vec![Op::Pop]]
+}
+
+#[test]
+fn state_machine_okay() {
+    Check::new()
+        .run_state_machine(m::default::<Op>(), corpus(), Model::default, || {
+            Model::default()
+        })
+        .unwrap();
+}
+
+#[test]
+fn state_machine_fail_and_shrink() {
+    let failure = Check::new()
+        .iters(10_000)
+        .seed(0x1234)
+        .run_state_machine(
+            m::default::<Op>(),
+            corpus(),
+            Model::default,
+            BuggyStack::default,
+        )
+        .unwrap_err()
+        .unwrap_failed();
+
+    // The minimal failing sequence is five pushes followed by either a `Len` or
+    // a `Pop`.
+    assert_eq!(failure.value.len(), 6);
+    assert!(failure.value[..5]
+        .iter()
+        .all(|op| matches!(op, Op::Push(_))));
+    assert!(matches!(failure.value[5], Op::Len | Op::Pop));
+    assert!(failure.message.contains("observations differ"));
+}